@@ -4,7 +4,7 @@
 
 extern crate crowbook_text_processing;
 
-use crowbook_text_processing::{FrenchFormatter, clean, escape};
+use crowbook_text_processing::{TypographicFormatter, get_formatter, clean, escape};
 
 use std::env;
 use std::io;
@@ -14,15 +14,98 @@ use std::process::exit;
 const TOOLS: &'static[(&'static str, &'static str)] = &[
     ("escape_html", "escape text for HTML display"),
     ("escape_tex", "escape text for LaTeX display"),
+    ("escape_markdown", "escape text for Markdown display"),
+    ("escape_typst", "escape text for Typst display"),
+    ("escape_nb_spaces_typst", "escape non-breaking spaces using Typst syntax"),
+    ("escape_roff", "escape text for roff (groff, man pages) display"),
+    ("escape_nb_spaces_roff", "escape non-breaking spaces using roff escapes"),
+    ("escape_rtf", "escape text for RTF display"),
     ("escape_nb_spaces_html", "replace narrow non-breaking spaces with HTML elements"),
     ("escape_nb_spaces_tex", "escape non-breaking spaces using TeX symbol"),
     ("clean_ellipsis", "use unicode character ‘…’ for ellipsis"),
+    ("clean_primes", "replace straight quotes following numbers with primes (‘′’, ‘″’)"),
+    ("clean_math", "use math symbols for minus, multiplication, fractions and superscripts"),
     ("clean_quotes", "try to replace straight quotes with curly ones"),
+    ("clean_quotes_paragraphs", "same, with quotations spanning several lines"),
     ("ligature_dashes", "replace ‘--’ by ‘–’ and ‘---’ by ‘—’"),
     ("ligature_guillemets", "replace ‘<<’ by ‘«’ and ‘>>’ by ‘»’"),
+    ("ligature_symbols", "apply all the following symbol ligatures"),
+    ("ligature_marks", "replace ‘(c)’ by ‘©’, ‘(r)’ by ‘®’ and ‘(tm)’ by ‘™’"),
+    ("ligature_arrows", "replace ‘->’ by ‘→’, ‘<-’ by ‘←’ and ‘=>’ by ‘⇒’"),
+    ("ligature_comparisons", "replace ‘<=’ by ‘≤’, ‘>=’ by ‘≥’ and ‘!=’ by ‘≠’"),
     ("format_french", "try to apply french typographic rules"),
+    ("format_german", "try to apply german typographic rules"),
+    ("format_spanish", "try to apply spanish typographic rules"),
+    ("format_italian", "try to apply italian typographic rules"),
+    ("format_portuguese", "try to apply (brazilian) portuguese typographic rules"),
+    ("format_polish", "try to apply polish typographic rules"),
+    ("format_czech", "try to apply czech typographic rules"),
+    ("format_slovak", "try to apply slovak typographic rules"),
+    ("format_russian", "try to apply russian typographic rules"),
+    ("format_ukrainian", "try to apply ukrainian typographic rules"),
+    ("format_swedish", "try to apply swedish typographic rules"),
+    ("format_norwegian", "try to apply norwegian typographic rules"),
+    ("format_danish", "try to apply danish typographic rules"),
+    ("format_finnish", "try to apply finnish typographic rules"),
+    ("format_french_ca", "try to apply canadian french typographic rules"),
+    ("format_french_ch", "try to apply swiss french typographic rules"),
+    ("format_french_be", "try to apply belgian french typographic rules"),
+    ("format_chinese", "try to apply (simplified) chinese typographic rules"),
+    ("format_japanese", "try to apply japanese typographic rules"),
+    ("format_korean", "try to apply korean typographic rules"),
+    ("format_english", "try to apply (american) english typographic rules"),
+    ("format_british", "try to apply british english typographic rules"),
+    ("format_dutch", "try to apply dutch typographic rules"),
+    ("format_greek", "try to apply greek typographic rules"),
+    ("format_hungarian", "try to apply hungarian typographic rules"),
+    ("format_catalan", "try to apply catalan typographic rules"),
+    ("format_hebrew", "try to apply hebrew typographic rules"),
+    ("format_arabic", "try to apply arabic typographic rules"),
+    ("format:<lang>", "apply the typographic rules of a language, given as a BCP-47 tag"),
 ];
 
+/// Language tags of the `format_<language>` tools
+const FORMAT_TAGS: &'static[(&'static str, &'static str)] = &[
+    ("format_french", "fr"),
+    ("format_german", "de"),
+    ("format_spanish", "es"),
+    ("format_italian", "it"),
+    ("format_portuguese", "pt-BR"),
+    ("format_polish", "pl"),
+    ("format_czech", "cs"),
+    ("format_slovak", "sk"),
+    ("format_russian", "ru"),
+    ("format_ukrainian", "uk"),
+    ("format_swedish", "sv"),
+    ("format_norwegian", "no"),
+    ("format_danish", "da"),
+    ("format_finnish", "fi"),
+    ("format_french_ca", "fr-CA"),
+    ("format_french_ch", "fr-CH"),
+    ("format_french_be", "fr-BE"),
+    ("format_chinese", "zh"),
+    ("format_japanese", "ja"),
+    ("format_korean", "ko"),
+    ("format_english", "en"),
+    ("format_british", "en-GB"),
+    ("format_dutch", "nl"),
+    ("format_greek", "el"),
+    ("format_hungarian", "hu"),
+    ("format_catalan", "ca"),
+    ("format_hebrew", "he"),
+    ("format_arabic", "ar"),
+];
+
+/// Returns the formatter used by a `format_<language>` or `format:<lang>` tool, if it is one
+fn formatter_for_tool(tool: &str) -> Option<Box<dyn TypographicFormatter>> {
+    if tool.starts_with("format:") {
+        return Some(get_formatter(&tool["format:".len()..]));
+    }
+    FORMAT_TAGS.iter()
+        .find(|&&(name, _)| name == tool)
+        .map(|&(_, tag)| get_formatter(tag))
+}
+
 fn print_transformations() {
     for &(name, desc) in TOOLS {
         println!("    {name}: {desc}",
@@ -51,23 +134,50 @@ Valid transformations are the following:",
         println!("EXAMPLE: {bin} clean_quotes clean_ellipsis escape_html",
                  bin = args[0]);
     } else {
-        let french = FrenchFormatter::new();
+        // Build the formatters once, before reading the input
+        let formatters = args[1..].iter()
+            .map(|tool| formatter_for_tool(tool))
+            .collect::<Vec<_>>();
+
+        let mut paragraphs = clean::QuoteParagraphs::new(clean::QuoteStyle::default());
+        let mut marks = clean::SymbolLigatures::new();
+        marks.arrows(false).comparisons(false);
+        let mut arrows = clean::SymbolLigatures::new();
+        arrows.marks(false).comparisons(false);
+        let mut comparisons = clean::SymbolLigatures::new();
+        comparisons.marks(false).arrows(false);
 
         let stdin = io::stdin();
         for line in stdin.lock().lines() {
             let line = line.expect("Error reading from standard input");
             let mut output = clean::whitespaces(line);
-            for argument in &args[1..] {
+            for (argument, formatter) in args[1..].iter().zip(&formatters) {
+                if let Some(ref formatter) = *formatter {
+                    output = formatter.format(output);
+                    continue;
+                }
                 output = match argument.as_ref() {
                     "escape_html" => escape::html(output),
                     "escape_tex" => escape::tex(output),
+                    "escape_markdown" => escape::markdown(output),
+                    "escape_typst" => escape::typst(output),
+                    "escape_nb_spaces_typst" => escape::nb_spaces_typst(output),
+                    "escape_roff" => escape::roff(output),
+                    "escape_nb_spaces_roff" => escape::nb_spaces_roff(output),
+                    "escape_rtf" => escape::rtf(output),
                     "escape_nbsp" => escape::nb_spaces_html(output),
                     "escape_nb_spaces_tex" => escape::nb_spaces_tex(output),
                     "clean_quotes" => clean::quotes(output),
+                    "clean_quotes_paragraphs" => paragraphs.paragraph(output),
                     "clean_ellipsis" => clean::ellipsis(output),
-                    "format_french" => french.format(output),
+                    "clean_primes" => clean::primes(output),
+                    "clean_math" => clean::math(output),
                     "ligature_dashes" => clean::dashes(output),
                     "ligature_guillemets" => clean::guillemets(output),
+                    "ligature_symbols" => clean::symbols(output),
+                    "ligature_marks" => marks.apply(output),
+                    "ligature_arrows" => arrows.apply(output),
+                    "ligature_comparisons" => comparisons.apply(output),
                     t => {
                         println!("Error: transformation “{}” not recognized.", t);
                         println!("Valid transformations are:");
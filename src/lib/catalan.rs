@@ -0,0 +1,120 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::default::Default;
+
+use regex::Regex;
+
+use crate::common::Pipeline;
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used in catalan: «…», “…” and ‘…’
+const CATALAN_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('«', '»'),
+    secondary: ('“', '”'),
+    tertiary: Some(('‘', '’')),
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Catalan typographic formatter.
+///
+/// The purpose of this struct is to try to make a text more typographically correct,
+/// according to catalan typographic rules. This means:
+///
+/// * replacing straight quotes with catalan ones: `«…»`, and `“…”` then `‘…’` for
+///   nested quotes (single quotes, `'`, are also replaced by `“…”`), while elisions such
+///   as `l'home` or `d'aquí` use the typographic apostrophe;
+/// * using the punt volat (`·`) in the geminated l, when it was typed as a period or
+///   another similar character (e.g. `col.lecció` becomes `col·lecció`);
+/// * making spaces in numbers, e.g. `80 000` or `50 km` narrow and non-breaking.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::CatalanFormatter;
+/// let output = CatalanFormatter::new()
+///              .format("\"L'home de la col.lecció\", va dir.");
+/// assert_eq!(&output, "«L’home de la col·lecció», va dir.");
+/// ```
+#[derive(Debug)]
+pub struct CatalanFormatter {
+    /// Replace `l.l` with `l·l`
+    punt_volat: bool,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
+}
+
+impl Default for CatalanFormatter {
+    fn default() -> Self {
+        CatalanFormatter {
+            punt_volat: true,
+            pipeline: Pipeline::default(),
+        }
+    }
+}
+
+impl CatalanFormatter {
+    /// Create a new CatalanFormatter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// If set to true, replaces a period (or a similar character, such as `•`) between two
+    /// `l` inside a word with the punt volat (`·`), e.g. `col.lecció` becomes `col·lecció`.
+    ///
+    /// Default is true.
+    pub fn punt_volat(&mut self, b: bool) -> &mut Self {
+        self.punt_volat = b;
+        self
+    }
+
+    /// (Try to) Format a string according to catalan typographic rules.
+    pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        let mut input = self.pipeline.run(input.into(), &CATALAN_QUOTES);
+        if self.punt_volat {
+            input = punt_volat(input);
+        }
+
+        self.pipeline.numbers(input)
+    }
+}
+
+typographic_formatter!(CatalanFormatter, [tex, html, typst, numbers]);
+
+/// Replace a period, bullet or other dot between two `l` inside a word with the punt volat
+fn punt_volat(input: Cow<str>) -> Cow<str> {
+    lazy_static! {
+        static ref REGEX: Regex = Regex::new(
+            r"(\p{L}[lL])[.\x{2022}\x{2027}\x{2219}\x{22C5}]([lL]\p{L})").unwrap();
+    }
+    if REGEX.is_match(&input) {
+        Cow::Owned(REGEX.replace_all(&input, "${1}·${2}").into_owned())
+    } else {
+        input
+    }
+}
+
+
+#[test]
+fn catalan_quotes() {
+    let s = CatalanFormatter::new().format("\"L'home va dir 'hola' d'aquí\"");
+    assert_eq!(&s, "«L’home va dir “hola” d’aquí»");
+}
+
+#[test]
+fn catalan_punt_volat() {
+    let f = CatalanFormatter::new();
+
+    let s = f.format("La col.lecció i la IL•LUSTRACIÓ de la cel·la. L'altre...");
+    assert_eq!(&s, "La col·lecció i la IL·LUSTRACIÓ de la cel·la. L’altre…");
+
+    let s = CatalanFormatter::new()
+        .punt_volat(false)
+        .format("col.lecció");
+    assert_eq!(&s, "col.lecció");
+}
@@ -0,0 +1,412 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::default::Default;
+
+use crate::common::{is_whitespace, THIN_SPACE};
+use crate::common::Pipeline;
+use crate::escape;
+use crate::clean::{QuoteStyle, ENGLISH_QUOTES};
+
+/// Language used by `CjkFormatter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CjkLanguage {
+    /// Simplified chinese (zh-Hans, zh-CN), using `“…”` and `‘…’`
+    SimplifiedChinese,
+    /// Traditional chinese (zh-Hant, zh-TW, zh-HK), using `「…」` and `『…』`
+    TraditionalChinese,
+    /// Japanese (ja), using `「…」` and `『…』`
+    Japanese,
+    /// Korean (ko), using `“…”` and `‘…’`
+    Korean,
+}
+
+impl CjkLanguage {
+    /// Returns the (primary, secondary) quotation marks
+    fn quotes(self) -> ((char, char), (char, char)) {
+        match self {
+            CjkLanguage::SimplifiedChinese | CjkLanguage::Korean => (('“', '”'), ('‘', '’')),
+            CjkLanguage::TraditionalChinese | CjkLanguage::Japanese => (('「', '」'), ('『', '』')),
+        }
+    }
+}
+
+/// Chinese, japanese and korean typographic formatter.
+///
+/// The purpose of this struct is to try to make a text more typographically correct,
+/// according to the typographic rules of these languages. For chinese and japanese, this means:
+///
+/// * replacing half-width punctuation that follows a CJK character with its full-width
+///   version, e.g. `,` with `，` (or `、` in japanese), `.` with `。` or `?` with `？`;
+/// * replacing straight quotes with `“…”` (and `‘…’` for nested quotes) in simplified
+///   chinese, or `「…」` (and `『…』`) in traditional chinese and japanese. Since CJK text doesn't
+///   use spaces between words, quotes are assumed to alternate between opening and closing
+///   ones;
+/// * removing spaces between CJK characters, and around full-width punctuation;
+/// * optionally, inserting a thin space between CJK characters and latin letters or digits
+///   (see `pangu_spacing`).
+///
+/// Korean uses spaces between words and western punctuation, so only the "generic" rules and
+/// the spacing around chinese characters apply to it.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::{CjkFormatter, CjkLanguage};
+/// let output = CjkFormatter::new()
+///              .format("他说 \"你好\", 然后 走了.");
+/// assert_eq!(&output, "他说“你好”，然后走了。");
+/// let output = CjkFormatter::new()
+///              .language(CjkLanguage::Japanese)
+///              .format("彼は \"こんにちは\" と 言った.");
+/// assert_eq!(&output, "彼は「こんにちは」と言った。");
+/// ```
+#[derive(Debug)]
+pub struct CjkFormatter {
+    /// Chinese, japanese or korean
+    language: CjkLanguage,
+    /// Insert thin spaces between CJK and latin characters
+    pangu_spacing: bool,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
+}
+
+impl Default for CjkFormatter {
+    fn default() -> Self {
+        CjkFormatter {
+            language: CjkLanguage::SimplifiedChinese,
+            pangu_spacing: false,
+            pipeline: Pipeline::default(),
+        }
+    }
+}
+
+impl CjkFormatter {
+    /// Create a new CjkFormatter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the language (simplified or traditional chinese, japanese or korean).
+    ///
+    /// Default is `CjkLanguage::SimplifiedChinese`.
+    pub fn language(&mut self, language: CjkLanguage) -> &mut Self {
+        self.language = language;
+        self
+    }
+
+    /// If set to true, inserts a thin space between CJK characters and latin letters
+    /// or digits (the "pangu" rule), e.g. `使用Rust语言` becomes `使用 Rust 语言`.
+    ///
+    /// Existing spaces at these positions are also replaced by thin spaces. Since this space is
+    /// a breaking one, it doesn't prevent line breaks.
+    ///
+    /// Default is false.
+    pub fn pangu_spacing(&mut self, b: bool) -> &mut Self {
+        self.pangu_spacing = b;
+        self
+    }
+
+    /// (Try to) Format a string according to chinese, japanese or korean typographic rules.
+    pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        let is_korean = self.language == CjkLanguage::Korean;
+        // Quotes are handled separately, except for korean
+        let pipeline = Pipeline {
+            typographic_quotes: self.pipeline.typographic_quotes && is_korean,
+            .. self.pipeline.clone()
+        };
+        let input = pipeline.run(input.into(), &ENGLISH_QUOTES);
+        if !input.chars().any(is_wide) {
+            // Only latin text: use the generic quotes replacement, with the language's marks
+            return if self.pipeline.typographic_quotes && !is_korean {
+                let (primary, secondary) = self.language.quotes();
+                let style = QuoteStyle {
+                    primary,
+                    secondary,
+                    .. ENGLISH_QUOTES
+                };
+                self.pipeline.quotes(input, &style)
+            } else {
+                input
+            };
+        }
+
+        let mut chars = input.chars().collect::<Vec<_>>();
+        if !is_korean {
+            if self.pipeline.typographic_quotes {
+                self.format_quotes(&mut chars);
+            }
+            self.format_punctuation(&mut chars);
+        }
+        let mut chars = remove_spaces(&chars);
+        if self.pangu_spacing {
+            chars = add_thin_spaces(&chars);
+        }
+
+        let output = chars.into_iter().collect::<String>();
+        if output == input {
+            input
+        } else {
+            Cow::Owned(output)
+        }
+    }
+
+    /// (Try to) Format a string according to chinese, japanese or korean typographic rules,
+    /// and escape the characters that need to be escaped in HTML (e.g. &).
+    ///
+    /// Contrary to other formatters, this doesn't call `escape::nb_spaces_html`, since
+    /// wrapping words in `span` elements would prevent the browser from breaking lines
+    /// between CJK characters.
+    pub fn format_html<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        escape::html(self.format(input))
+    }
+
+    /// Replace straight quotes (and curly ones, if they differ) with the quotation marks
+    /// of the language
+    fn format_quotes(&self, chars: &mut [char]) {
+        let ((primary, secondary), apostrophe) = match self.pipeline.quote_style {
+            Some(style) => ((style.primary, style.secondary), style.apostrophe),
+            None => (self.language.quotes(), '’'),
+        };
+        let mut double_opened = false;
+        let mut single_opened = false;
+        for i in 0..chars.len() {
+            let c = chars[i];
+            match c {
+                '"' => {
+                    chars[i] = if double_opened { primary.1 } else { primary.0 };
+                    double_opened = !double_opened;
+                }
+                '“' => {
+                    chars[i] = primary.0;
+                    double_opened = true;
+                }
+                '”' => {
+                    chars[i] = primary.1;
+                    double_opened = false;
+                }
+                '\'' | '‘' | '’' => {
+                    let is_apostrophe = i > 0 && i + 1 < chars.len()
+                        && is_latin(chars[i - 1]) && is_latin(chars[i + 1]);
+                    if is_apostrophe {
+                        chars[i] = apostrophe;
+                    } else {
+                        let opening = match c {
+                            '‘' => true,
+                            '’' => false,
+                            _ => !single_opened,
+                        };
+                        chars[i] = if opening { secondary.0 } else { secondary.1 };
+                        single_opened = opening;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Replace half-width punctuation following a CJK character with full-width punctuation
+    fn format_punctuation(&self, chars: &mut [char]) {
+        for i in 1..chars.len() {
+            let next = chars.get(i + 1).cloned();
+            let replacement = match chars[i] {
+                ',' if self.language == CjkLanguage::Japanese => '、',
+                ',' => '，',
+                '.' => {
+                    // Not part of "..." or of a number or a domain name
+                    match next {
+                        Some(c) if c == '.' || c.is_alphanumeric() => continue,
+                        _ => '。',
+                    }
+                }
+                '?' => '？',
+                '!' => '！',
+                ':' => '：',
+                ';' => '；',
+                _ => continue,
+            };
+            if follows_cjk(chars, i) {
+                chars[i] = replacement;
+            }
+        }
+    }
+}
+
+typographic_formatter!(CjkFormatter, [tex, typst], quote_style:
+    ///
+    /// Except for korean and for text without CJK characters, only the primary and secondary
+    /// marks and the apostrophe of `style` are used: its tertiary marks, spacing, nesting and
+    /// elisions are ignored.
+);
+
+/// Returns true if the character is a chinese ideograph or a japanese kana
+fn is_ideograph(c: char) -> bool {
+    matches!(c,
+             '\u{3040}'..='\u{30FF}' // Hiragana and Katakana
+             | '\u{31F0}'..='\u{31FF}' // Katakana extension
+             | '\u{3400}'..='\u{4DBF}' // CJK extension A
+             | '\u{4E00}'..='\u{9FFF}' // CJK unified ideographs
+             | '\u{F900}'..='\u{FAFF}' // CJK compatibility ideographs
+             | '\u{20000}'..='\u{2FA1F}') // CJK extensions B to F
+}
+
+/// Returns true if the character is a full-width punctuation mark, including CJK brackets
+fn is_wide_punctuation(c: char) -> bool {
+    matches!(c,
+             '\u{3001}'..='\u{303F}' // CJK symbols and punctuation
+             | '\u{FF01}'..='\u{FF0F}' // Full-width forms...
+             | '\u{FF1A}'..='\u{FF20}'
+             | '\u{FF3B}'..='\u{FF40}'
+             | '\u{FF5B}'..='\u{FF65}')
+}
+
+/// Returns true if the character is a CJK character or punctuation mark
+fn is_wide(c: char) -> bool {
+    is_ideograph(c) || is_wide_punctuation(c)
+}
+
+/// Returns true if the character is a latin letter or a digit
+fn is_latin(c: char) -> bool {
+    c.is_ascii_alphanumeric() || (matches!(c, 'À'..='ɏ') && c.is_alphabetic())
+}
+
+/// Returns true if the character is a quotation mark that can be used both in CJK and
+/// in latin text
+fn is_ambiguous_quote(c: char) -> bool {
+    matches!(c, '"' | '\'' | '“' | '”' | '‘' | '’')
+}
+
+/// Returns true if the character at index `n` follows a CJK character (possibly
+/// separated by spaces, closing quotes or parenthesis)
+fn follows_cjk(chars: &[char], n: usize) -> bool {
+    chars[..n].iter()
+        .rev()
+        .find(|c| !is_whitespace(**c) && !is_ambiguous_quote(**c) && **c != ')')
+        .is_some_and(|c| is_wide(*c))
+}
+
+/// Remove spaces between CJK characters (ignoring quotes that can be used in latin text) and
+/// around full-width punctuation
+fn remove_spaces(chars: &[char]) -> Vec<char> {
+    let mut res: Vec<char> = Vec::with_capacity(chars.len());
+    for (i, c) in chars.iter().enumerate() {
+        if is_whitespace(*c) {
+            let prev = res.last().cloned();
+            let next = chars.get(i + 1).cloned();
+            if prev.is_some_and(is_wide_punctuation) || next.is_some_and(is_wide_punctuation) {
+                continue;
+            }
+            let prev = res.iter().rev().find(|c| !is_ambiguous_quote(**c));
+            let next = chars[i + 1..].iter().find(|c| !is_ambiguous_quote(**c));
+            if prev.is_some_and(|c| is_wide(*c)) && next.is_some_and(|c| is_wide(*c)) {
+                continue;
+            }
+        }
+        res.push(*c);
+    }
+    res
+}
+
+/// Insert (or replace existing spaces with) thin spaces between ideographs and latin
+/// letters or digits
+fn add_thin_spaces(chars: &[char]) -> Vec<char> {
+    let is_boundary = |a: char, b: char| (is_ideograph(a) && is_latin(b))
+        || (is_latin(a) && is_ideograph(b));
+    let mut res: Vec<char> = Vec::with_capacity(chars.len());
+    for (i, c) in chars.iter().enumerate() {
+        let prev = res.last().cloned();
+        let next = chars.get(i + 1).cloned();
+        match (prev, next) {
+            (Some(prev), Some(next)) if is_whitespace(*c) && is_boundary(prev, next) => {
+                res.push(THIN_SPACE);
+                continue;
+            }
+            (Some(prev), _) if is_boundary(prev, *c) => res.push(THIN_SPACE),
+            _ => {}
+        }
+        res.push(*c);
+    }
+    res
+}
+
+
+#[test]
+fn chinese() {
+    let s = CjkFormatter::new()
+        .format("我 喜欢 \"读书\"; 你呢? 他说: '好的'!");
+    assert_eq!(&s, "我喜欢“读书”；你呢？他说：‘好的’！");
+}
+
+#[test]
+fn chinese_traditional() {
+    let s = CjkFormatter::new()
+        .language(CjkLanguage::TraditionalChinese)
+        .format("他說 “我 喜歡 ‘讀書’”.");
+    assert_eq!(&s, "他說「我喜歡『讀書』」。");
+}
+
+#[test]
+fn japanese() {
+    let s = CjkFormatter::new()
+        .language(CjkLanguage::Japanese)
+        .format("はい, そうです. 3.14 は 数字です...");
+    assert_eq!(&s, "はい、そうです。3.14 は数字です…");
+}
+
+#[test]
+fn cjk_latin() {
+    // Spaces between CJK and latin are kept, punctuation after latin text is unchanged
+    let s = CjkFormatter::new()
+        .format("我用 Rust, it's \"great\".");
+    assert_eq!(&s, "我用 Rust, it’s “great”.");
+}
+
+#[test]
+fn cjk_quote_style() {
+    let s = CjkFormatter::new()
+        .quote_style(crate::clean::QuoteStyle {
+            primary: ('「', '」'),
+            secondary: ('『', '』'),
+            apostrophe: '\'',
+            .. crate::clean::QuoteStyle::default()
+        })
+        .format("他说\"it's 'ok'\"");
+    assert_eq!(&s, "他说「it's『ok』」");
+}
+
+#[test]
+fn cjk_latin_only() {
+    let s = CjkFormatter::new().format("I said \"hello\", 'tis ok");
+    assert_eq!(&s, "I said “hello”, ’tis ok");
+
+    let s = CjkFormatter::new()
+        .language(CjkLanguage::Japanese)
+        .add_elisions(&["'ello"])
+        .format("I said \"'ello\"");
+    assert_eq!(&s, "I said 「’ello」");
+}
+
+#[test]
+fn korean() {
+    let s = CjkFormatter::new()
+        .language(CjkLanguage::Korean)
+        .format("그는 \"안녕하세요\"라고 말했다. 漢字 漢字");
+    assert_eq!(&s, "그는 “안녕하세요”라고 말했다. 漢字漢字");
+}
+
+#[test]
+fn pangu() {
+    let s = CjkFormatter::new()
+        .pangu_spacing(true)
+        .format("使用Rust 语言编写了2个程序");
+    assert_eq!(&s, "使用\u{2009}Rust\u{2009}语言编写了\u{2009}2\u{2009}个程序");
+}
+
+#[test]
+fn cjk_html() {
+    let s = CjkFormatter::new()
+        .format_html("<b>你好</b> & 再见 !");
+    assert_eq!(&s, "&lt;b&gt;你好&lt;/b&gt; &amp; 再见！");
+}
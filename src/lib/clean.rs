@@ -13,7 +13,7 @@
 //! assert_eq!(&output, "Some ‘text’ whose formatting could be enhanced…");
 //! ```
 
-use regex::Regex;
+use regex::{Captures, Regex};
 
 use std::borrow::Cow;
 
@@ -136,6 +136,13 @@ pub fn ellipsis<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
 /// that easy (and, in some circumstances, impossible without understanding the meaning
 /// of the text) to get right.
 ///
+/// The straight quotes of the elided english words of `ENGLISH_ELISIONS` (e.g. `'tis` or
+/// `'em`) are always replaced with apostrophes, unless the word is itself quoted (e.g.
+/// `'round'`). Use `quotes_with` and a `QuoteStyle` without elisions to avoid it.
+///
+/// Straight quotes following numbers are replaced with primes first (see `primes`), so e.g.
+/// `5'10"` becomes `5′10″`.
+///
 /// # Example
 ///
 /// ```
@@ -146,29 +153,262 @@ pub fn ellipsis<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
 /// assert_eq!(&s, "‘foo’");
 /// ```
 pub fn quotes<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    quotes_with(input, &ENGLISH_QUOTES)
+}
+
+/// Quotation marks (and apostrophe) used to replace straight quotes by `quotes_with` and by
+/// the language formatters.
+///
+/// The default style is the one used by `quotes` (`“…”`, `‘…’` and `’`).
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean::QuoteStyle;
+/// let style = QuoteStyle {
+///     primary: ('«', '»'),
+///     secondary: ('“', '”'),
+///     .. QuoteStyle::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteStyle {
+    /// Opening and closing marks replacing double quotes
+    pub primary: (char, char),
+    /// Opening and closing marks replacing single quotes
+    pub secondary: (char, char),
+    /// If set, double quotes nested inside double quotes alternate between
+    /// primary, secondary and tertiary marks
+    pub tertiary: Option<(char, char)>,
+    /// Character replacing apostrophes (e.g. in `it's`)
+    pub apostrophe: char,
+    /// If set, the space inserted after opening marks and before closing marks (replacing the
+    /// existing spaces, if any)
+    pub spacing: Option<char>,
+    /// If true, marks depend on the nesting depth of quotes instead of the straight quote
+    /// that was typed: primary marks for outermost quotes, secondary marks inside them, then
+    /// tertiary marks (if set), deeper quotes alternating between the last two levels.
+    ///
+    /// If nesting is unbalanced, a closing quote closes the innermost quote opened with the
+    /// same straight quote (or else the innermost quote), and a closing double quote without
+    /// any opened quote gets the primary closing mark.
+    pub nesting: bool,
+    /// Elided words whose straight quotes are always apostrophes (e.g. `'tis` or `rock 'n'
+    /// roll`), regardless of what follows them. Matching is case-insensitive, and only
+    /// whole words match.
+    pub elisions: &'static [&'static str],
+}
+
+impl QuoteStyle {
+    /// Returns the marks used for double quotes at the given nesting level
+    fn double(&self, level: usize) -> (char, char) {
+        match (level, self.tertiary) {
+            (_, None) | (0, _) => self.primary,
+            (1, _) => self.secondary,
+            (_, Some(tertiary)) => tertiary,
+        }
+    }
+
+    /// Returns the marks used at the given nesting depth, when `nesting` is set
+    fn nested(&self, depth: usize) -> (char, char) {
+        let levels = match self.tertiary {
+            Some(tertiary) => vec![self.primary, self.secondary, tertiary],
+            None => vec![self.primary, self.secondary],
+        };
+        let n = levels.len();
+        if depth < n {
+            levels[depth]
+        } else {
+            levels[n - 2 + (depth - (n - 2)) % 2]
+        }
+    }
+}
+
+impl Default for QuoteStyle {
+    fn default() -> Self {
+        ENGLISH_QUOTES
+    }
+}
+
+/// Quotation marks used by `quotes`
+pub(crate) const ENGLISH_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('“', '”'),
+    secondary: ('‘', '’'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: ENGLISH_ELISIONS,
+};
+
+/// Elided english words used by `quotes`, whose straight quotes are apostrophes
+pub const ENGLISH_ELISIONS: &[&str] = &["'tis", "'twas", "'twere", "'twill", "'twould",
+                                        "'em", "'n'", "'n", "n'", "ol'", "'cause", "'cos",
+                                        "'til", "'bout", "'round", "'scuse", "'kay", "o'"];
+
+/// Returns, for each character, whether it is an apostrophe of one of the elided `words`
+fn elided<'w, I: IntoIterator<Item = &'w str>>(chars: &[char], words: I) -> Vec<bool> {
+    let mut mask = vec![false; chars.len()];
+    for word in words {
+        let word = word.chars()
+            .map(|c| if c == '’' { '\'' } else { c })
+            .collect::<Vec<_>>();
+        if word.len() > chars.len() {
+            continue;
+        }
+        let opens = word[0] == '\'';
+        let closes = word[word.len() - 1] == '\'';
+        for start in 0..=(chars.len() - word.len()) {
+            let end = start + word.len();
+            if (start > 0 && chars[start - 1].is_alphanumeric())
+                || (end < chars.len() && chars[end].is_alphanumeric()) {
+                    continue;
+                }
+            if opens && closes {
+                // A quoted word (e.g. `'n'`) is only elided between two words
+                let between_words = start > 1 && is_whitespace(chars[start - 1])
+                    && chars[start - 2].is_alphanumeric()
+                    && end + 1 < chars.len() && is_whitespace(chars[end])
+                    && chars[end + 1].is_alphanumeric();
+                if !between_words {
+                    continue;
+                }
+            } else if (opens && chars.get(end) == Some(&'\''))
+                || (closes && start > 0 && chars[start - 1] == '\'') {
+                    // The word is quoted (e.g. `'round'`), not elided
+                    continue;
+                }
+            let matches = word.iter()
+                .zip(&chars[start..end])
+                .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()));
+            if matches {
+                for (k, c) in word.iter().enumerate() {
+                    if *c == '\'' {
+                        mask[start + k] = true;
+                    }
+                }
+            }
+        }
+    }
+    mask
+}
+
+/// Push an opening mark, followed by the space inside quotes, if any.
+///
+/// Returns true if the following whitespaces must be skipped.
+fn push_opening(new_s: &mut String, style: &QuoteStyle, mark: char) -> bool {
+    new_s.push(mark);
+    if let Some(space) = style.spacing {
+        new_s.push(space);
+    }
+    style.spacing.is_some()
+}
+
+/// Push a closing mark, preceded by the space inside quotes, if any
+fn push_closing(new_s: &mut String, style: &QuoteStyle, mark: char) {
+    if let Some(space) = style.spacing {
+        while new_s.ends_with(is_whitespace) {
+            new_s.pop();
+        }
+        new_s.push(space);
+    }
+    new_s.push(mark);
+}
+
+/// Returns the index of the single quote that would close the one at index `n`, if any
+///
+/// Quotes opened in between (which will be apostrophes) are skipped along with their closing
+/// quote, unless they are never closed.
+fn find_closing_single(chars: &[char], elided: &[bool], n: usize) -> Option<usize> {
+    let mut first = None;
+    let mut depth = 0;
+    for j in (n + 1)..chars.len() {
+        if chars[j] == '\'' && !elided[j] {
+            if chars[j-1].is_whitespace() {
+                if j < chars.len() - 1 && char_class(chars[j+1]) == CharClass::Alphanumeric {
+                    depth += 1;
+                }
+            } else if j >= chars.len() - 1
+                || char_class(chars[j+1]) != CharClass::Alphanumeric {
+                    if depth == 0 {
+                        return Some(j);
+                    }
+                    depth -= 1;
+                    first = first.or(Some(j));
+                }
+        }
+    }
+    first
+}
+
+/// Replace straight quotes with the quotation marks of `style`, using the same heuristics
+/// as `quotes`.
+///
+/// As with `quotes`, straight quotes following numbers are replaced with primes.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean;
+/// use crowbook_text_processing::clean::QuoteStyle;
+/// let style = QuoteStyle {
+///     primary: ('«', '»'),
+///     secondary: ('‹', '›'),
+///     spacing: Some('\u{202F}'),
+///     .. QuoteStyle::default()
+/// };
+/// let s = clean::quotes_with("\"L'été 'indien'\"", &style);
+/// assert_eq!(&s, "«\u{202F}L’été ‹\u{202F}indien\u{202F}›\u{202F}»");
+/// ```
+pub fn quotes_with<'a, S: Into<Cow<'a, str>>>(input: S, style: &QuoteStyle) -> Cow<'a, str> {
+    quotes_with_elisions(input, style, &[])
+}
+
+/// Replace straight quotes with the quotation marks of `style`, treating the straight quotes
+/// of the elided `words` (in addition to `style.elisions`) as apostrophes.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean;
+/// use crowbook_text_processing::clean::QuoteStyle;
+/// let s = clean::quotes_with_elisions("'Tis 'Arry's 'at, 'e said", &QuoteStyle::default(),
+///                                     &["'arry", "'at", "'e"]);
+/// assert_eq!(&s, "’Tis ’Arry’s ’at, ’e said");
+/// ```
+pub fn quotes_with_elisions<'a, S: Into<Cow<'a, str>>>(input: S, style: &QuoteStyle,
+                                                       words: &[&str]) -> Cow<'a, str> {
     lazy_static! {
         static ref REGEX: Regex = Regex::new("[\"\']").unwrap();
     }
-    let input = input.into();
+    let input = primes_in(input.into(), false);
     let first = REGEX.find(&input)
         .map(|mat| mat.start());
     if let Some(mut first) = first {
         let mut new_s = String::with_capacity(input.len());
-        if first > 0 {
-            // Move one step backward since we might need to know if previous char was
-            // a letter or not
-            first -= 1;
-            // Check that it is a character boundary, else go backward
-            while !input.is_char_boundary(first) {
-                first -= 1;
-            }
-        }
+        // Move backward to the previous non-alphanumeric char, since we might need to know
+        // if previous char was a letter or not, and to match elided words
+        first = input[0..first].char_indices()
+            .rev()
+            .find(|(_, c)| !c.is_alphanumeric())
+            .map_or(0, |(n, _)| n);
         new_s.push_str(&input[0..first]);
         let mut chars = input[first..].chars().collect::<Vec<_>>();
+        let elided = elided(&chars, style.elisions.iter().chain(words).copied());
+        if style.nesting {
+            quotes_nested(&chars, &elided, style, &mut new_s, &mut vec![]);
+            return Cow::Owned(new_s);
+        }
         let mut closing_quote = None;
         let mut opened_doubles = 0;
+        // Set after an opening mark, if spaces must be replaced
+        let mut skip_whitespaces = false;
         for i in 0..chars.len() {
             let c = chars[i];
+            if skip_whitespaces && is_whitespace(c) {
+                continue;
+            }
+            skip_whitespaces = false;
             let has_opened_quote = if let Some(n) = closing_quote {
                 i <= n
             } else {
@@ -188,15 +428,16 @@ pub fn quotes<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
                     };
 
                     if prev < next {
+                        skip_whitespaces = push_opening(&mut new_s, style, style.double(opened_doubles).0);
                         opened_doubles += 1;
-                        new_s.push('“');
                     } else if opened_doubles > 0 {
                         opened_doubles -= 1;
-                        new_s.push('”');
+                        push_closing(&mut new_s, style, style.double(opened_doubles).1);
                     } else {
                         new_s.push('"');
                     }
                 }
+                '\'' if elided[i] => new_s.push(style.apostrophe),
                 '\'' => {
                     let prev = if i > 0 {
                         char_class(chars[i - 1])
@@ -213,41 +454,39 @@ pub fn quotes<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
                         // Elision or possessive
                         (CharClass::Alphanumeric, CharClass::Alphanumeric)
 //                            | (CharClass::Punctuation, CharClass::Alphanumeric)
-                            => '’',
+                            => style.apostrophe,
 
                         // Beginning of word, it's opening (not always though)
                         (x, y) if x < y
                             => {
-                                let mut is_next_closing = false;
-                                for j in (i + 1)..chars.len() {
-                                    if chars[j] == '\'' {
-                                        if chars[j-1].is_whitespace() {
-                                            continue;
-                                        } else if j >= chars.len() - 1
-                                            || char_class(chars[j+1]) != CharClass::Alphanumeric {
-                                                is_next_closing = true;
-                                                closing_quote = Some(j);
-                                                chars[j] = '’';
-                                                break;
-                                            }
-                                    }
-                                }
-                                if is_next_closing && !has_opened_quote {
-                                    '‘'
+                                // Single quotes can't be nested, so an opening quote inside
+                                // another one (and its closing quote) is an apostrophe
+                                let closing = if has_opened_quote {
+                                    None
+                                } else {
+                                    find_closing_single(&chars, &elided, i)
+                                };
+                                if let Some(j) = closing {
+                                    closing_quote = Some(j);
+                                    chars[j] = style.secondary.1;
+                                    skip_whitespaces = push_opening(&mut new_s, style,
+                                                                    style.secondary.0);
+                                    continue;
                                 } else {
-                                    '’'
+                                    style.apostrophe
                                 }
                             }
 
                         // Apostrophe at end of word, it's closing
                         (x, y) if x > y
                             => {
-                                '’'
+                                style.apostrophe
                             },
                         _ => '\'',
                     };
                     new_s.push(replacement);
                 }
+                _ if closing_quote == Some(i) => push_closing(&mut new_s, style, c),
                 _ => new_s.push(c),
             }
         }
@@ -257,6 +496,309 @@ pub fn quotes<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
     }
 }
 
+/// Returns the marks of a quote opened with the straight quote `c`, inside the quotes
+/// opened with `opened`
+fn marks_for(style: &QuoteStyle, opened: &[char], c: char) -> (char, char) {
+    if style.nesting {
+        style.nested(opened.len())
+    } else if c == '"' {
+        style.double(opened.iter().filter(|q| **q == '"').count())
+    } else {
+        style.secondary
+    }
+}
+
+/// Replace straight quotes while keeping track of opened quotes.
+///
+/// `opened` contains the straight quotes of the quotes that are still opened, and is
+/// updated at the end of `chars`.
+fn quotes_nested(chars: &[char], elided: &[bool], style: &QuoteStyle, new_s: &mut String,
+                 opened: &mut Vec<char>) {
+    // Set if the previous character was a quote (to true if it was an opening one)
+    let mut last_quote: Option<bool> = None;
+    let mut skip_whitespaces = false;
+    for (i, &c) in chars.iter().enumerate() {
+        let previous_quote = last_quote.take();
+        if skip_whitespaces && is_whitespace(c) {
+            continue;
+        }
+        skip_whitespaces = false;
+        if c != '"' && c != '\'' {
+            new_s.push(c);
+            continue;
+        }
+        if elided[i] {
+            new_s.push(style.apostrophe);
+            continue;
+        }
+
+        let prev = if i > 0 {
+            char_class(chars[i - 1])
+        } else {
+            CharClass::Whitespace
+        };
+        let next = if i < chars.len() - 1 {
+            char_class(chars[i + 1])
+        } else {
+            CharClass::Whitespace
+        };
+        let opening = match (prev, next) {
+            // Elision or possessive
+            (CharClass::Alphanumeric, CharClass::Alphanumeric) if c == '\'' => None,
+            (x, y) if x < y => Some(true),
+            (x, y) if x > y => Some(false),
+            // Same class on both sides: follow the previous quote if there is one
+            _ => match (previous_quote, prev) {
+                (Some(b), _) => Some(b),
+                (None, CharClass::Whitespace) => None,
+                (None, _) => Some(opened.is_empty()),
+            },
+        };
+
+        match opening {
+            // A single quote only opens a quote if it is closed later
+            Some(true) if c == '"' || find_closing_single(chars, elided, i).is_some() => {
+                let mark = marks_for(style, opened, c).0;
+                opened.push(c);
+                skip_whitespaces = push_opening(new_s, style, mark);
+                last_quote = Some(true);
+            }
+            Some(false) if c == '"' || opened.contains(&c) => {
+                let depth = opened.iter()
+                    .rposition(|q| *q == c)
+                    .unwrap_or_else(|| opened.len().saturating_sub(1));
+                let q = opened.get(depth).copied().unwrap_or(c);
+                opened.truncate(depth);
+                push_closing(new_s, style, marks_for(style, opened, q).1);
+                last_quote = Some(false);
+            }
+            _ if c == '\'' && (prev != CharClass::Whitespace
+                               || next == CharClass::Alphanumeric) => {
+                new_s.push(style.apostrophe);
+            }
+            _ => new_s.push(c),
+        }
+    }
+}
+
+/// Replace straight quotes in a sequence of paragraphs, carrying the opened quotes from one
+/// paragraph to the next.
+///
+/// A quotation spanning several paragraphs is usually reopened at the start of each new
+/// paragraph without having been closed at the end of the previous one: such a reopening
+/// quote gets the same mark as the quote it continues, instead of being considered as a
+/// nested quote. Optionally, a continuation mark (e.g. `»` in French) can be used for this
+/// reopening quote, or inserted at the start of paragraphs continuing a quotation.
+///
+/// Contrary to `quotes_with`, this always keeps track of the quotes that are opened, so
+/// the results may slightly differ on unbalanced quotes.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean::{QuoteParagraphs, QuoteStyle};
+/// let mut paragraphs = QuoteParagraphs::new(QuoteStyle::default());
+/// assert_eq!(&paragraphs.paragraph("\"It started in Paris,"), "“It started in Paris,");
+/// assert_eq!(&paragraphs.paragraph("\"Then it rained.\""), "“Then it rained.”");
+///
+/// let style = QuoteStyle {
+///     primary: ('«', '»'),
+///     spacing: Some('\u{A0}'),
+///     .. QuoteStyle::default()
+/// };
+/// let mut paragraphs = QuoteParagraphs::new(style);
+/// paragraphs.continuation(Some('»'));
+/// assert_eq!(&paragraphs.paragraph("\"Il pleuvait."), "«\u{A0}Il pleuvait.");
+/// assert_eq!(&paragraphs.paragraph("Puis il neigea.\""), "»\u{A0}Puis il neigea.\u{A0}»");
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuoteParagraphs {
+    style: QuoteStyle,
+    continuation: Option<char>,
+    elisions: Vec<String>,
+    opened: Vec<char>,
+}
+
+impl QuoteParagraphs {
+    /// Create a new `QuoteParagraphs`, using the quotation marks of `style`
+    pub fn new(style: QuoteStyle) -> Self {
+        QuoteParagraphs {
+            style,
+            continuation: None,
+            elisions: vec![],
+            opened: vec![],
+        }
+    }
+
+    /// Sets the mark used at the start of paragraphs continuing a quotation.
+    ///
+    /// **Default**: `None`, the quotation is only reopened if the paragraph starts
+    /// with a quote.
+    pub fn continuation(&mut self, mark: Option<char>) -> &mut Self {
+        self.continuation = mark;
+        self
+    }
+
+    /// Adds elided words whose straight quotes are apostrophes (see `quotes_with_elisions`)
+    pub fn elisions(&mut self, words: &[&str]) -> &mut Self {
+        self.elisions.extend(words.iter().map(|w| w.to_string()));
+        self
+    }
+
+    /// Forget the quotes that are still opened, e.g. at the end of a chapter
+    pub fn reset(&mut self) -> &mut Self {
+        self.opened.clear();
+        self
+    }
+
+    /// Returns true if a quotation is still opened at the end of the last paragraph
+    pub fn is_quoting(&self) -> bool {
+        !self.opened.is_empty()
+    }
+
+    /// Replace straight quotes in the next paragraph
+    pub fn paragraph<'a, S: Into<Cow<'a, str>>>(&mut self, input: S) -> Cow<'a, str> {
+        let input = primes_in(input.into(), self.opened.contains(&'"'));
+        if input.trim().is_empty() {
+            return input;
+        }
+        let continuing = self.is_quoting();
+        if !input.contains(['"', '\'']) && (!continuing || self.continuation.is_none()) {
+            return input;
+        }
+
+        let chars = input.chars().collect::<Vec<_>>();
+        let mut new_s = String::with_capacity(input.len());
+        let start = chars.iter().position(|c| !is_whitespace(*c)).unwrap();
+        new_s.extend(&chars[..start]);
+        let mut rest = &chars[start..];
+        if continuing {
+            let reopening = (rest[0] == '"' || rest[0] == '\'')
+                && rest.get(1).is_some_and(|c| !is_whitespace(*c));
+            if reopening {
+                rest = &rest[1..];
+            }
+            let mark = match self.continuation {
+                Some(mark) => Some(mark),
+                None if reopening => Some(marks_for(&self.style, &[], self.opened[0]).0),
+                None => None,
+            };
+            if let Some(mark) = mark {
+                if push_opening(&mut new_s, &self.style, mark) {
+                    let n = rest.iter().position(|c| !is_whitespace(*c)).unwrap_or(rest.len());
+                    rest = &rest[n..];
+                }
+            }
+        }
+        let elided = elided(rest, self.style.elisions.iter()
+                            .copied()
+                            .chain(self.elisions.iter().map(|w| w.as_str())));
+        quotes_nested(rest, &elided, &self.style, &mut new_s, &mut self.opened);
+        Cow::Owned(new_s)
+    }
+}
+
+/// Replace straight quotes in a sequence of paragraphs, using `QuoteParagraphs`.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean;
+/// use crowbook_text_processing::clean::QuoteStyle;
+/// let v = clean::quotes_paragraphs(vec!["\"First,", "\"second.\""], &QuoteStyle::default());
+/// assert_eq!(v, vec!["“First,", "“second.”"]);
+/// ```
+pub fn quotes_paragraphs<'a, I>(paragraphs: I, style: &QuoteStyle) -> Vec<Cow<'a, str>>
+where I: IntoIterator,
+      I::Item: Into<Cow<'a, str>> {
+    let mut state = QuoteParagraphs::new(*style);
+    paragraphs.into_iter()
+        .map(|p| state.paragraph(p))
+        .collect()
+}
+
+/// Replace straight quotes following numbers with primes (`′`), double primes (`″`) or triple
+/// primes (`‴`), e.g. in measurements, coordinates or times.
+///
+/// Only quotes directly following a digit, and not followed by a letter, are replaced, and a
+/// quote that closes a quotation (e.g. in `"Top 10"`) is left alone. This is also done
+/// by `quotes` and `quotes_with`.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean;
+/// let s = clean::primes("5'10\", 45° 30' 15\" and 2'30''");
+/// assert_eq!(&s, "5′10″, 45° 30′ 15″ and 2′30″");
+/// let s = clean::primes("\"Top 10\" of the '80s");
+/// assert_eq!(&s, "\"Top 10\" of the '80s");
+/// ```
+pub fn primes<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    primes_in(input.into(), false)
+}
+
+/// Replace primes, `quoting` being set if a double quote is opened before `input`
+fn primes_in(input: Cow<str>, mut quoting: bool) -> Cow<str> {
+    lazy_static! {
+        static ref REGEX: Regex = Regex::new("[0-9][\"\']").unwrap();
+    }
+    if !REGEX.is_match(&input) {
+        return input;
+    }
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut new_s = String::with_capacity(input.len());
+    let no_elisions = vec![false; chars.len()];
+    // Number of opened single quotes
+    let mut opened_singles = 0_usize;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let after_digit = i > 0 && chars[i - 1].is_ascii_digit();
+        let before_letter = |n: usize| n < chars.len() && chars[n].is_alphabetic();
+        match c {
+            '"' if after_digit && !quoting && !before_letter(i + 1) => new_s.push('″'),
+            '"' => {
+                quoting = !quoting;
+                new_s.push(c);
+            }
+            '\'' if after_digit => {
+                let n = chars[i..].iter()
+                    .take(3)
+                    .take_while(|c| **c == '\'')
+                    .count();
+                if before_letter(i + n) {
+                    // e.g. `1990's`
+                    new_s.push(c);
+                } else if n == 1 && opened_singles > 0 {
+                    opened_singles -= 1;
+                    new_s.push(c);
+                } else {
+                    new_s.push(['′', '″', '‴'][n - 1]);
+                    i += n - 1;
+                }
+            }
+            '\'' => {
+                let prev = if i > 0 {
+                    char_class(chars[i - 1])
+                } else {
+                    CharClass::Whitespace
+                };
+                let next_alphanumeric = chars.get(i + 1).is_some_and(|c| c.is_alphanumeric());
+                if prev != CharClass::Alphanumeric && next_alphanumeric
+                    && find_closing_single(&chars, &no_elisions, i).is_some() {
+                        opened_singles += 1;
+                    } else if prev == CharClass::Alphanumeric && !next_alphanumeric {
+                        opened_singles = opened_singles.saturating_sub(1);
+                    }
+                new_s.push(c);
+            }
+            _ => new_s.push(c),
+        }
+        i += 1;
+    }
+    Cow::Owned(new_s)
+}
+
 
 /// Replace double dashes (`--`) and triple dashes (`---`) to en dash and em dash, respectively.
 ///
@@ -352,6 +894,488 @@ pub fn guillemets<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
     }
 }
 
+/// Replaces ASCII sequences with the symbols they stand for:
+///
+/// * marks: `(c)` with `©` (only before a year or after `Copyright`), `(r)` with `®` and
+///   `(tm)` with `™` (only directly after a word);
+/// * arrows: `->` with `→`, `<-` with `←` and `=>` with `⇒`;
+/// * comparisons: `<=` with `≤`, `>=` with `≥` and `!=` with `≠`.
+///
+/// Since these sequences are common in code, arrows and comparisons are only replaced when
+/// surrounded by whitespace, and nothing is replaced inside inline code (between backticks).
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean::SymbolLigatures;
+/// let mut ligatures = SymbolLigatures::new();
+/// ligatures.comparisons(false);
+/// let s = ligatures.apply("Crowbook(tm) (c) 2024: x <= y => `a->b` -> c");
+/// assert_eq!(&s, "Crowbook™ © 2024: x <= y ⇒ `a->b` → c");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SymbolLigatures {
+    marks: bool,
+    arrows: bool,
+    comparisons: bool,
+}
+
+impl Default for SymbolLigatures {
+    fn default() -> Self {
+        SymbolLigatures {
+            marks: true,
+            arrows: true,
+            comparisons: true,
+        }
+    }
+}
+
+impl SymbolLigatures {
+    /// Create new `SymbolLigatures`, replacing all sequences
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether `(c)`, `(r)` and `(tm)` are replaced.
+    ///
+    /// **Default**: `true`
+    pub fn marks(&mut self, b: bool) -> &mut Self {
+        self.marks = b;
+        self
+    }
+
+    /// Sets whether `->`, `<-` and `=>` are replaced.
+    ///
+    /// **Default**: `true`
+    pub fn arrows(&mut self, b: bool) -> &mut Self {
+        self.arrows = b;
+        self
+    }
+
+    /// Sets whether `<=`, `>=` and `!=` are replaced.
+    ///
+    /// **Default**: `true`
+    pub fn comparisons(&mut self, b: bool) -> &mut Self {
+        self.comparisons = b;
+        self
+    }
+
+    /// Replace the enabled sequences in `input`
+    pub fn apply<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        lazy_static! {
+            static ref REGEX: Regex = Regex::new(r"(?i)\((c|r|tm)\)|->|<-|=>|<=|>=|!=").unwrap();
+        }
+        let input = input.into();
+        if !REGEX.is_match(&input) {
+            return input;
+        }
+        // Inline code spans, as pairs of backticks
+        let backticks = input.match_indices('`')
+            .map(|(n, _)| n)
+            .collect::<Vec<_>>();
+        let mut spans = backticks.chunks_exact(2).peekable();
+
+        let mut new_s = String::with_capacity(input.len());
+        let mut last = 0;
+        for mat in REGEX.find_iter(&input) {
+            // Matches are in order, so skip the code spans that end before this one
+            while spans.peek().is_some_and(|pair| pair[1] < mat.start()) {
+                spans.next();
+            }
+            if spans.peek().is_some_and(|pair| pair[0] < mat.start()) {
+                continue;
+            }
+            let before = &input[..mat.start()];
+            let after = &input[mat.end()..];
+            let spaced = before.chars().next_back().is_none_or(char::is_whitespace)
+                && after.chars().next().is_none_or(char::is_whitespace);
+            let after_word = before.chars()
+                .next_back()
+                .is_some_and(char::is_alphanumeric);
+            let before_year = after.starts_with(' ')
+                && after[1..].starts_with(|c: char| c.is_ascii_digit());
+            let trimmed = before.trim_end();
+            let after_copyright = trimmed.len() >= 9
+                && trimmed.is_char_boundary(trimmed.len() - 9)
+                && trimmed[trimmed.len() - 9..].eq_ignore_ascii_case("copyright");
+            let symbol = match mat.as_str().to_lowercase().as_str() {
+                "(c)" if self.marks && (before_year || after_copyright) => '©',
+                "(r)" if self.marks && after_word => '®',
+                "(tm)" if self.marks && after_word => '™',
+                "->" if self.arrows && spaced => '→',
+                "<-" if self.arrows && spaced => '←',
+                "=>" if self.arrows && spaced => '⇒',
+                "<=" if self.comparisons && spaced => '≤',
+                ">=" if self.comparisons && spaced => '≥',
+                "!=" if self.comparisons && spaced => '≠',
+                _ => continue,
+            };
+            new_s.push_str(&input[last..mat.start()]);
+            new_s.push(symbol);
+            last = mat.end();
+        }
+        if last == 0 {
+            return input;
+        }
+        new_s.push_str(&input[last..]);
+        Cow::Owned(new_s)
+    }
+}
+
+/// Replaces `(c)`, `(r)`, `(tm)`, arrows and comparison operators with the matching
+/// symbols, using the default `SymbolLigatures`.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean;
+/// let s = clean::symbols("Crowbook(r): 1 != 2 -> true");
+/// assert_eq!(&s, "Crowbook®: 1 ≠ 2 → true");
+/// ```
+pub fn symbols<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    SymbolLigatures::new().apply(input)
+}
+
+/// Replaces ASCII math notations with the matching symbols, in technical texts:
+///
+/// * plus-minus: `+/-` with `±` (see `math_plus_minus`);
+/// * minus: a hyphen before a number with `−` (see `math_minus`);
+/// * times: `x` between two numbers with `×` (see `math_times`);
+/// * superscripts: `^2` with `²` (see `math_superscripts`);
+/// * fractions: `1/2` with `½` (see `math_fractions`).
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean::MathSymbols;
+/// let mut symbols = MathSymbols::new();
+/// symbols.fractions(false);
+/// let s = symbols.apply("3 x 4 m^2 is not -5 +/- 1/2");
+/// assert_eq!(&s, "3 × 4 m² is not −5 ± 1/2");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MathSymbols {
+    plus_minus: bool,
+    minus: bool,
+    times: bool,
+    superscripts: bool,
+    fractions: bool,
+}
+
+impl Default for MathSymbols {
+    fn default() -> Self {
+        MathSymbols {
+            plus_minus: true,
+            minus: true,
+            times: true,
+            superscripts: true,
+            fractions: true,
+        }
+    }
+}
+
+impl MathSymbols {
+    /// Create new `MathSymbols`, applying all rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether `+/-` is replaced.
+    ///
+    /// **Default**: `true`
+    pub fn plus_minus(&mut self, b: bool) -> &mut Self {
+        self.plus_minus = b;
+        self
+    }
+
+    /// Sets whether hyphens before numbers are replaced.
+    ///
+    /// **Default**: `true`
+    pub fn minus(&mut self, b: bool) -> &mut Self {
+        self.minus = b;
+        self
+    }
+
+    /// Sets whether `x` between numbers is replaced.
+    ///
+    /// **Default**: `true`
+    pub fn times(&mut self, b: bool) -> &mut Self {
+        self.times = b;
+        self
+    }
+
+    /// Sets whether `^` followed by a digit is replaced.
+    ///
+    /// **Default**: `true`
+    pub fn superscripts(&mut self, b: bool) -> &mut Self {
+        self.superscripts = b;
+        self
+    }
+
+    /// Sets whether fractions are replaced.
+    ///
+    /// **Default**: `true`
+    pub fn fractions(&mut self, b: bool) -> &mut Self {
+        self.fractions = b;
+        self
+    }
+
+    /// Apply the enabled rules to `input`
+    pub fn apply<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        let mut input = input.into();
+        if self.plus_minus {
+            input = math_plus_minus(input);
+        }
+        if self.minus {
+            input = math_minus(input);
+        }
+        if self.times {
+            input = math_times(input);
+        }
+        if self.superscripts {
+            input = math_superscripts(input);
+        }
+        if self.fractions {
+            input = math_fractions(input);
+        }
+        input
+    }
+}
+
+/// Apply all the `math_*` transformations: `math_plus_minus`, `math_minus`, `math_times`,
+/// `math_superscripts` and `math_fractions`, using the default `MathSymbols`.
+///
+/// Use `MathSymbols` if only some rules are wanted.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean;
+/// let s = clean::math("3 x 4 m^2 is not -5 +/- 1/2 x-ray");
+/// assert_eq!(&s, "3 × 4 m² is not −5 ± ½ x-ray");
+/// ```
+pub fn math<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    MathSymbols::new().apply(input)
+}
+
+/// Replace `+/-` with `±`
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean;
+/// let s = clean::math_plus_minus("5 +/- 2");
+/// assert_eq!(&s, "5 ± 2");
+/// ```
+pub fn math_plus_minus<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    let input = input.into();
+    if input.contains("+/-") {
+        Cow::Owned(input.replace("+/-", "±"))
+    } else {
+        input
+    }
+}
+
+/// Replace a hyphen directly before a number with a minus sign (`−`).
+///
+/// The hyphen must not follow a letter or a number, so `COVID-19` or `1990-2000` are left
+/// alone, and neither are ranges with a space before the hyphen (e.g. `10 -20`), unless they
+/// are part of a list of numbers (e.g. `-1 -2 -3`).
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean;
+/// let s = clean::math_minus("It was -5 °C (-23 °F) at COVID-19's peak");
+/// assert_eq!(&s, "It was −5 °C (−23 °F) at COVID-19's peak");
+/// ```
+pub fn math_minus<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    lazy_static! {
+        static ref REGEX: Regex = Regex::new(r"(^|[\s(\[=<>+*/×:;,])-([0-9])").unwrap();
+        // Rest of a number followed by another negative number
+        static ref LIST: Regex = Regex::new(r"^[0-9.,]*\s-[0-9]").unwrap();
+    }
+    let input = input.into();
+    if !REGEX.is_match(&input) {
+        return input;
+    }
+    let new_s = REGEX.replace_all(&input, |caps: &Captures| {
+        let start = caps.get(0).unwrap().start();
+        let end = caps.get(0).unwrap().end();
+        let is_number_part = |c: char| c.is_ascii_digit() || c == '.' || c == ',';
+        // Number before the hyphen, and what precedes it
+        let before = input[..start].trim_end_matches(is_number_part);
+        let is_negative = before.strip_suffix(['-', '\u{2212}'])
+            .is_some_and(|s| !s.ends_with(char::is_alphanumeric));
+        let is_followed = LIST.is_match(&input[end..]);
+        let is_range = caps[1].chars().all(char::is_whitespace)
+            && input[..start].ends_with(|c: char| c.is_ascii_digit())
+            && !is_negative
+            && !is_followed;
+        if is_range {
+            caps[0].to_string()
+        } else {
+            format!("{}\u{2212}{}", &caps[1], &caps[2])
+        }
+    });
+    Cow::Owned(new_s.into_owned())
+}
+
+/// Replace a `x` between two numbers with a multiplication sign (`×`).
+///
+/// There can be at most one space on each side of the `x`. Hexadecimal numbers (e.g.
+/// `0x1F`) and words such as `x-ray` are left alone.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean;
+/// let s = clean::math_times("3 x 4 x 5, 1920x1080 but 0x1F");
+/// assert_eq!(&s, "3 × 4 × 5, 1920×1080 but 0x1F");
+/// ```
+pub fn math_times<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    lazy_static! {
+        static ref REGEX: Regex = Regex::new(r"[0-9] ?x ?[0-9]").unwrap();
+    }
+    let input = input.into();
+    if !REGEX.is_match(&input) {
+        return input;
+    }
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut new_s = String::with_capacity(input.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c == 'x' {
+            // Index of the number before and after the `x`, skipping one space
+            let before = match i.checked_sub(1) {
+                Some(j) if chars[j] == ' ' => j.checked_sub(1),
+                j => j,
+            };
+            let after = match chars.get(i + 1) {
+                Some(' ') => i + 2,
+                _ => i + 1,
+            };
+            let is_number = |j: usize| chars.get(j).is_some_and(|c| c.is_ascii_digit());
+            let is_hexadecimal = i > 0 && before == Some(i - 1) && chars[i - 1] == '0'
+                && (i < 2 || !chars[i - 2].is_alphanumeric());
+            if before.is_some_and(is_number) && is_number(after) && !is_hexadecimal {
+                new_s.push('×');
+                continue;
+            }
+        }
+        new_s.push(c);
+    }
+    Cow::Owned(new_s)
+}
+
+/// Replace `^` followed by a digit with the superscript digit (e.g. `m^2` with `m²`).
+///
+/// The `^` must follow a letter, a digit or a closing parenthesis, and must not be followed
+/// by more than one digit, so that markdown footnotes such as `[^2]` are left alone.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean;
+/// let s = clean::math_superscripts("10 m^2, (a+b)^3 and a note[^2]");
+/// assert_eq!(&s, "10 m², (a+b)³ and a note[^2]");
+/// ```
+pub fn math_superscripts<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    lazy_static! {
+        static ref REGEX: Regex = Regex::new(r"[\w)]\^[0-9]").unwrap();
+    }
+    let input = input.into();
+    if !REGEX.is_match(&input) {
+        return input;
+    }
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut new_s = String::with_capacity(input.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let digit = chars.get(i + 1).and_then(|c| c.to_digit(10));
+        let is_exponent = c == '^'
+            && i > 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == ')')
+            && !chars.get(i + 2).is_some_and(|c| c.is_ascii_digit());
+        match digit {
+            Some(digit) if is_exponent => {
+                new_s.push(['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'][digit as usize]);
+                i += 2;
+            }
+            _ => {
+                new_s.push(c);
+                i += 1;
+            }
+        }
+    }
+    Cow::Owned(new_s)
+}
+
+/// Replace fractions such as `1/2` with the matching unicode character (`½`), or, when
+/// there is none, with the numbers separated by a fraction slash (`⁄`).
+///
+/// Fractions that are part of a date, a path or a word (e.g. `01/05/2020`, `a/1/2` or
+/// `A4/5`) are left alone.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::clean;
+/// let s = clean::math_fractions("1/2 cup, 3/4 hour and 5/12 of 12/05/2020");
+/// assert_eq!(&s, "½ cup, ¾ hour and 5⁄12 of 12/05/2020");
+/// ```
+pub fn math_fractions<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    lazy_static! {
+        static ref REGEX: Regex = Regex::new(r"[0-9]+/[0-9]+").unwrap();
+    }
+    let input = input.into();
+    let bytes = input.as_bytes();
+    let fractions = REGEX.find_iter(&input)
+        .filter(|mat| {
+            let before = input[..mat.start()].chars().next_back();
+            let after = input[mat.end()..].chars().next();
+            !before.is_some_and(|c| c.is_alphanumeric() || "/.,".contains(c))
+                && !after.is_some_and(|c| c.is_alphanumeric() || c == '/')
+                && bytes[mat.as_str().find('/').unwrap() + mat.start() + 1] != b'0'
+        })
+        .collect::<Vec<_>>();
+    if fractions.is_empty() {
+        return input;
+    }
+    let mut new_s = String::with_capacity(input.len());
+    let mut last = 0;
+    for mat in fractions {
+        new_s.push_str(&input[last..mat.start()]);
+        let fraction = match mat.as_str() {
+            "1/2" => "½",
+            "1/3" => "⅓",
+            "2/3" => "⅔",
+            "1/4" => "¼",
+            "3/4" => "¾",
+            "1/5" => "⅕",
+            "2/5" => "⅖",
+            "3/5" => "⅗",
+            "4/5" => "⅘",
+            "1/6" => "⅙",
+            "5/6" => "⅚",
+            "1/7" => "⅐",
+            "1/8" => "⅛",
+            "3/8" => "⅜",
+            "5/8" => "⅝",
+            "7/8" => "⅞",
+            "1/9" => "⅑",
+            "1/10" => "⅒",
+            s => {
+                new_s.push_str(&s.replace('/', "⁄"));
+                ""
+            }
+        };
+        new_s.push_str(fraction);
+        last = mat.end();
+    }
+    new_s.push_str(&input[last..]);
+    Cow::Owned(new_s)
+}
+
 
 
 #[test]
@@ -437,12 +1461,185 @@ fn quotes_12() {
     assert_eq!(&s, "A double quote--“within” dashes--would be nice.");
 }
 
+#[test]
+fn quotes_elisions() {
+    let s = quotes("L'uomo dell'anno, 'l'arte', un po' di pane.");
+    assert_eq!(&s, "L’uomo dell’anno, ‘l’arte’, un po’ di pane.");
+}
+
 #[test]
 fn quotes_13() {
     let s = quotes("A double quote–\"within\" dashes–would be nice.");
     assert_eq!(&s, "A double quote–“within” dashes–would be nice.");
 }
 
+#[test]
+fn quotes_with_style() {
+    let style = QuoteStyle {
+        primary: ('「', '」'),
+        secondary: ('『', '』'),
+        apostrophe: '\'',
+        .. QuoteStyle::default()
+    };
+    let s = quotes_with("\"It's 'here'\"", &style);
+    assert_eq!(&s, "「It's 『here』」");
+
+    let style = QuoteStyle {
+        primary: ('«', '»'),
+        spacing: Some('\u{A0}'),
+        .. QuoteStyle::default()
+    };
+    let s = quotes_with("Il dit \"Non \" et 'oui'.", &style);
+    assert_eq!(&s, "Il dit «\u{A0}Non\u{A0}» et ‘\u{A0}oui\u{A0}’.");
+}
+
+#[test]
+fn quotes_nesting() {
+    let style = QuoteStyle {
+        nesting: true,
+        .. QuoteStyle::default()
+    };
+    let s = quotes_with("\"He said \"she said \"no\"\"\" and left", &style);
+    assert_eq!(&s, "“He said ‘she said “no”’” and left");
+    let s = quotes_with("'It's 'fine', he said'", &style);
+    assert_eq!(&s, "“It’s ‘fine’, he said”");
+    let s = quotes_with("It is \"the dogs' bone\"", &style);
+    assert_eq!(&s, "It is “the dogs’ bone”");
+
+    let style = QuoteStyle {
+        primary: ('«', '»'),
+        secondary: ('“', '”'),
+        tertiary: Some(('‘', '’')),
+        nesting: true,
+        .. QuoteStyle::default()
+    };
+    let s = quotes_with("\"a 'b \"c 'd' c\" b' a\"", &style);
+    assert_eq!(&s, "«a “b ‘c “d” c’ b” a»");
+
+    // Unbalanced nesting
+    let s = quotes_with("\"a \"b\" c\"\" d \"e", &style);
+    assert_eq!(&s, "«a “b” c»» d «e");
+}
+
+#[test]
+fn quotes_elided_words() {
+    let s = quotes("'Tis the season, 'twas said");
+    assert_eq!(&s, "’Tis the season, ’twas said");
+    let s = quotes("Give 'em some rock 'n' roll, ol' boy");
+    assert_eq!(&s, "Give ’em some rock ’n’ roll, ol’ boy");
+    let s = quotes("\"'Tis 'fine' and ol' man's 'em\"");
+    assert_eq!(&s, "“’Tis ‘fine’ and ol’ man’s ’em”");
+    // Only whole words match
+    let s = quotes("'tiser'");
+    assert_eq!(&s, "‘tiser’");
+    // Quoted words are not elided
+    let s = quotes("She said 'round' is the word, not 'cause', 'o' or 'n'.");
+    assert_eq!(&s, "She said ‘round’ is the word, not ‘cause’, ‘o’ or ‘n’.");
+
+    let s = quotes_with_elisions("'Enry said 'ello", &QuoteStyle::default(), &["'enry", "'ello"]);
+    assert_eq!(&s, "’Enry said ’ello");
+    let style = QuoteStyle {
+        nesting: true,
+        .. QuoteStyle::default()
+    };
+    let s = quotes_with("'Twas 'fine', 'em", &QuoteStyle { elisions: &["'twas"], .. style });
+    assert_eq!(&s, "’Twas “fine”, ’em");
+
+    let mut paragraphs = QuoteParagraphs::new(style);
+    paragraphs.elisions(&["'arry"]);
+    assert_eq!(&paragraphs.paragraph("'Arry sang rock 'n' roll"), "’Arry sang rock ’n’ roll");
+}
+
+#[test]
+fn primes_0() {
+    let s = primes("He is 5'10\" tall, at 48° 51' 24\" N, and ran it in 2'30''");
+    assert_eq!(&s, "He is 5′10″ tall, at 48° 51′ 24″ N, and ran it in 2′30″");
+    let s = primes("At 1h 2'3'''");
+    assert_eq!(&s, "At 1h 2′3‴");
+    // Genuine quotations
+    let s = primes("\"Top 10\", 'Top 5', the 1990's and the '80s are 12\" long");
+    assert_eq!(&s, "\"Top 10\", 'Top 5', the 1990's and the '80s are 12″ long");
+
+    let s = quotes("He said: \"I'm 5'10, 'really'.\" At 45° 30' 15\"");
+    assert_eq!(&s, "He said: “I’m 5′10, ‘really’.” At 45° 30′ 15″");
+    let mut paragraphs = QuoteParagraphs::new(QuoteStyle::default());
+    assert_eq!(&paragraphs.paragraph("\"Chapter 1"), "“Chapter 1");
+    assert_eq!(&paragraphs.paragraph("and chapter 2\" of 12\""), "and chapter 2” of 12″");
+}
+
+#[test]
+fn symbols_0() {
+    let s = symbols("Copyright (C) Foo(TM), (a) (b) (c) options");
+    assert_eq!(&s, "Copyright © Foo™, (a) (b) (c) options");
+    let s = symbols("if (a->b >= 2 && c!=d) { x => y } <!-- comment --> `x <= y` a <= b");
+    assert_eq!(&s, "if (a->b ≥ 2 && c!=d) { x ⇒ y } <!-- comment --> `x <= y` a ≤ b");
+    let mut ligatures = SymbolLigatures::new();
+    ligatures.marks(false).arrows(false);
+    let s = ligatures.apply("Foo(r) <- 2 != 3");
+    assert_eq!(&s, "Foo(r) <- 2 ≠ 3");
+    let s = symbols("Press (r) to reload, option (tm) here, or Foo(r)");
+    assert_eq!(&s, "Press (r) to reload, option (tm) here, or Foo®");
+    let s = symbols("`a->b` x -> y `c->d` COPYRIGHT (c) e -> f");
+    assert_eq!(&s, "`a->b` x → y `c->d` COPYRIGHT © e → f");
+}
+
+#[test]
+fn math_0() {
+    let s = math("Take 3 x-rays, -2 x 3 = -6, and 2 x 1/3 mm^2 +/- 0x10");
+    assert_eq!(&s, "Take 3 x-rays, −2 × 3 = −6, and 2 × ⅓ mm² ± 0x10");
+    let s = math("Pages 10-12, on 3/12/2021, 1/0, some/1/2 and ^2");
+    assert_eq!(&s, "Pages 10-12, on 3/12/2021, 1/0, some/1/2 and ^2");
+    let s = math_fractions("24/7 and 1/2");
+    assert_eq!(&s, "24⁄7 and ½");
+    let s = math_times("x 3 x 4");
+    assert_eq!(&s, "x 3 × 4");
+    let s = math_times("x3 x 4");
+    assert_eq!(&s, "x3 × 4");
+    let s = math_superscripts("x^2y^2 + z^3");
+    assert_eq!(&s, "x²y² + z³");
+    let s = math_minus("From 10 -20 to 10 - 20, but = -5 and at -3");
+    assert_eq!(&s, "From 10 -20 to 10 - 20, but = −5 and at −3");
+    let s = math("Temps -1 -2 -3, and 1 -2 -3");
+    assert_eq!(&s, "Temps −1 −2 −3, and 1 −2 −3");
+    let s = math_minus("Temps \u{2212}1 -2");
+    assert_eq!(&s, "Temps \u{2212}1 \u{2212}2");
+    let s = MathSymbols::new()
+        .minus(false)
+        .superscripts(false)
+        .apply("-2 x 3 m^2 +/- 1/2");
+    assert_eq!(&s, "-2 × 3 m^2 ± ½");
+}
+
+#[test]
+fn quotes_multiple_paragraphs() {
+    let v = quotes_paragraphs(vec!["He said: \"First 'point',",
+                                   "",
+                                   "\"second point,",
+                                   "and 'third' point.\" Then he left."],
+                              &QuoteStyle::default());
+    assert_eq!(v, vec!["He said: “First ‘point’,",
+                       "",
+                       "“second point,",
+                       "and ‘third’ point.” Then he left."]);
+
+    let style = QuoteStyle {
+        primary: ('«', '»'),
+        secondary: ('“', '”'),
+        nesting: true,
+        .. QuoteStyle::default()
+    };
+    let mut paragraphs = QuoteParagraphs::new(style);
+    paragraphs.continuation(Some('»'));
+    assert_eq!(&paragraphs.paragraph("Il dit : \"Ce \"truc\""), "Il dit : «Ce “truc”");
+    assert!(paragraphs.is_quoting());
+    assert_eq!(&paragraphs.paragraph("\"n'est pas"), "»n’est pas");
+    assert_eq!(&paragraphs.paragraph("fini.\" Non."), "»fini.» Non.");
+    assert!(!paragraphs.is_quoting());
+    assert_eq!(&paragraphs.paragraph("\"Non."), "«Non.");
+    paragraphs.reset();
+    assert_eq!(&paragraphs.paragraph("Oui."), "Oui.");
+}
+
 
 #[test]
 fn ellipsis_0() {
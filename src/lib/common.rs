@@ -2,9 +2,15 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with
 // this file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
+use std::borrow::Cow;
+
+use crate::clean;
+use crate::clean::QuoteStyle;
+
 pub const NB_CHAR: char = ' '; // non breaking space
 pub const NB_CHAR_NARROW: char = '\u{202F}'; // narrow non breaking space
 pub const NB_CHAR_EM: char = '\u{2002}'; // demi em space
+pub const THIN_SPACE: char = '\u{2009}'; // thin (breaking) space
 
 
 /// Custom function because we don't really want to touch \t or \n
@@ -13,3 +19,224 @@ pub const NB_CHAR_EM: char = '\u{2002}'; // demi em space
 pub fn is_whitespace(c: char) -> bool {
     c == ' ' || c == ' ' || c == ' '
 }
+
+/// Returns true if the character is a dash (`-`, `–` or `—`)
+pub fn is_dash(c: char) -> bool {
+    matches!(c, '-' | '–' | '—')
+}
+
+/// If there is a dash (`-`, `--`, `---`, `–` or `—`) at index `n`, returns the index
+/// following it
+pub fn dash_end(v: &[char], n: usize) -> Option<usize> {
+    match v.get(n) {
+        Some('—') | Some('–') => Some(n + 1),
+        Some('-') => {
+            let len = v[n..].iter()
+                .take(3)
+                .take_while(|c| **c == '-')
+                .count();
+            Some(n + len)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the index of the first non-whitespace character in `v` from index `n`
+pub fn skip_whitespaces(v: &[char], n: usize) -> usize {
+    let mut n = n;
+    while n < v.len() && is_whitespace(v[n]) {
+        n += 1;
+    }
+    n
+}
+
+/// The "generic" (not specific to a language) steps that are run by formatters
+/// before applying language-specific rules.
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    /// Enable typographic apostrophe
+    pub typographic_quotes: bool,
+    /// Enaple typographic ellipsis
+    pub typographic_ellipsis: bool,
+    /// Enable dashes replacement
+    pub ligature_dashes: bool,
+    /// Enable guillemets replacement
+    pub ligature_guillemets: bool,
+    /// Quotation marks replacing the ones of the language
+    pub quote_style: Option<QuoteStyle>,
+    /// Elided words whose straight quotes are apostrophes, in addition to the ones of the
+    /// quotation marks
+    pub elisions: Vec<String>,
+    /// After that number of characters, assume it's not a currency
+    pub threshold_currency: usize,
+    /// After that number of characters, assume it's not an unit
+    pub threshold_unit: usize,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Pipeline {
+            typographic_quotes: true,
+            typographic_ellipsis: true,
+            ligature_dashes: false,
+            ligature_guillemets: false,
+            quote_style: None,
+            elisions: vec![],
+            threshold_currency: 3,
+            threshold_unit: 2,
+        }
+    }
+}
+
+impl Pipeline {
+    /// Returns the quotation marks set by the user, or `default` (the ones of the language)
+    pub fn quote_style<'s>(&'s self, default: &'s QuoteStyle) -> &'s QuoteStyle {
+        self.quote_style.as_ref().unwrap_or(default)
+    }
+
+    /// Remove unnecessary whitespaces and apply the enabled steps, replacing straight
+    /// quotes with `style` (unless the user set other quotation marks).
+    pub fn run<'a>(&self, input: Cow<'a, str>, style: &QuoteStyle) -> Cow<'a, str> {
+        let mut input = clean::whitespaces(input); // first pass to remove whitespaces
+
+        if self.ligature_dashes {
+            input = clean::dashes(input);
+        }
+
+        if self.ligature_guillemets {
+            input = clean::guillemets(input);
+        }
+
+        if self.typographic_quotes {
+            input = self.quotes(input, style);
+        }
+
+        if self.typographic_ellipsis {
+            input = clean::ellipsis(input);
+        }
+
+        input
+    }
+
+    /// Replace straight quotes with `style` (unless the user set other quotation marks),
+    /// treating the elided words as apostrophes.
+    pub fn quotes<'a>(&self, input: Cow<'a, str>, style: &QuoteStyle) -> Cow<'a, str> {
+        let elisions = self.elisions.iter()
+            .map(|w| w.as_str())
+            .collect::<Vec<_>>();
+        clean::quotes_with_elisions(input, self.quote_style(style), &elisions)
+    }
+
+    /// Make spaces in numbers (and between a number and a currency or unit) narrow and
+    /// non-breaking. See `format_numbers`.
+    pub fn numbers<'a>(&self, input: Cow<'a, str>) -> Cow<'a, str> {
+        if let Some(first) = input.chars().position(|c| c.is_ascii_digit()) {
+            let mut chars = input.chars().collect::<Vec<_>>();
+            format_numbers(&mut chars, first, NB_CHAR_NARROW,
+                           self.threshold_currency, self.threshold_unit);
+            Cow::Owned(chars.into_iter().collect())
+        } else {
+            input
+        }
+    }
+}
+
+/// Replace spaces inside numbers (e.g. `10 000`) and between a number and a
+/// symbol, currency or unit (e.g. `50 €`, `10 EUR` or `20 km`) with `nb_char`.
+///
+/// `first` is the position of the first digit in `chars`.
+pub fn format_numbers(chars: &mut [char], first: usize, nb_char: char,
+                      threshold_currency: usize, threshold_unit: usize) {
+    let mut is_number_series = false;
+
+    // Go back one step
+    let first = first.saturating_sub(1);
+    for i in first..(chars.len().saturating_sub(1)) {
+        let current = chars[i];
+        let next = chars[i + 1];
+
+        match current {
+            '0'..='9' => {
+                if i == 0 || !chars[i - 1].is_alphabetic() {
+                    is_number_series = true;
+                }
+            }
+            c if c.is_whitespace() => {
+                if is_number_series &&
+                    (next.is_ascii_digit() ||
+                     char_is_symbol(chars, i + 1, threshold_currency, threshold_unit)) {
+                        // Next char is a number or symbol such as $, and previous was number
+                        chars[i] = nb_char;
+                    }
+            }
+            _ => {
+                is_number_series = false;
+            }
+        }
+    }
+}
+
+/// Return true if the character is a symbol that is used after number
+/// and should have a nb_char before
+pub fn char_is_symbol(v: &[char], i: usize, threshold_currency: usize,
+                      threshold_unit: usize) -> bool {
+    let is_next_letter = if i < v.len() - 1 {
+        v[i + 1].is_alphabetic()
+    } else {
+        false
+    };
+    if is_next_letter {
+        match v[i] {
+            '°' => true,
+            c if c.is_uppercase() => {
+                let word = get_next_word(v, i);
+                if word.len() > threshold_currency {
+                    // not a currency
+                    false
+                } else {
+                    // if all uppercase and less than THRESHOLD,
+                    // assume it's a currency or a unit
+                    word.iter().all(|c| c.is_uppercase())
+                }
+            }
+            c if c.is_alphabetic() => {
+                let word = get_next_word(v, i);
+                // if two letters, assume it is a unit
+                word.len() <= threshold_unit
+            }
+            _ => false,
+        }
+    } else {
+        match v[i] {
+            c if (!c.is_alphabetic() && !c.is_whitespace()) => true, // special symbol
+            c if c.is_uppercase() => true, //single uppercase letter
+            _ => false,
+        }
+    }
+}
+
+/// Returns the next word in `v` starting from index `n`
+pub fn get_next_word(v: &[char], n: usize) -> &[char] {
+    let mut beginning = n;
+    let mut end = v.len();
+
+    for (i, car) in v.iter()
+        .enumerate()
+        .skip(n) {
+        if car.is_alphabetic() {
+            beginning = i;
+            break;
+        }
+    }
+
+    for (i, car) in v.iter()
+        .enumerate()
+        .skip(beginning) {
+        if car.is_whitespace() {
+            end = i - 1;
+            break;
+        }
+    }
+
+    &v[beginning..end]
+}
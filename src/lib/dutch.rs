@@ -0,0 +1,115 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::default::Default;
+
+use crate::common::Pipeline;
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used in dutch: “…” and ‘…’
+const DUTCH_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('“', '”'),
+    secondary: ('‘', '’'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: DUTCH_ELISIONS,
+};
+
+/// Alternative quotation marks used in dutch: ‘…’ and “…”
+const DUTCH_SINGLE_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('‘', '’'),
+    secondary: ('“', '”'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: DUTCH_ELISIONS,
+};
+
+/// Elided dutch words (`des`, `het` and `een`), whose straight quotes are apostrophes
+const DUTCH_ELISIONS: &[&str] = &["'s", "'t", "'n"];
+
+/// Dutch typographic formatter.
+///
+/// The purpose of this struct is to try to make a text more typographically correct,
+/// according to dutch typographic rules. This means:
+///
+/// * replacing straight quotes with `“…”` (and `‘…’` for nested quotes), or `‘…’` (and `“…”`)
+///   if the `single_quotes` option is set;
+/// * using the typographic apostrophe in the `’s`, `’t` and `’n` forms (e.g. `’s avonds` or
+///   `’s-Hertogenbosch`), which would otherwise be mistaken for opening quotes;
+/// * making spaces in numbers, e.g. `80 000` or `50 km` narrow and non-breaking.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::DutchFormatter;
+/// let output = DutchFormatter::new()
+///              .format_tex("\"'s Avonds loop ik 10 000 stappen\", zei hij.");
+/// assert_eq!(&output, "“’s Avonds loop ik 10\\,000 stappen”, zei hij.");
+/// ```
+#[derive(Debug, Default)]
+pub struct DutchFormatter {
+    /// Use ‘…’ instead of “…” for primary quotes
+    single_quotes: bool,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
+}
+
+impl DutchFormatter {
+    /// Create a new DutchFormatter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// If set to true, replaces straight double quotes with `‘…’` (and single quotes, used for
+    /// nested quotes, with `“…”`) instead of `“…”` (and `‘…’`).
+    ///
+    /// Default is false.
+    pub fn single_quotes(&mut self, b: bool) -> &mut Self {
+        self.single_quotes = b;
+        self
+    }
+
+    /// (Try to) Format a string according to dutch typographic rules.
+    pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        let marks = if self.single_quotes {
+            &DUTCH_SINGLE_QUOTES
+        } else {
+            &DUTCH_QUOTES
+        };
+        let input = self.pipeline.run(input.into(), marks);
+
+        self.pipeline.numbers(input)
+    }
+}
+
+typographic_formatter!(DutchFormatter, [tex, html, typst, numbers]);
+
+
+#[test]
+fn dutch_quotes() {
+    let s = DutchFormatter::new().format("\"Hij zei 'hallo'\", en Anna's kat.");
+    assert_eq!(&s, "“Hij zei ‘hallo’”, en Anna’s kat.");
+
+    let s = DutchFormatter::new()
+        .single_quotes(true)
+        .format("\"Hij zei 'hallo'\"");
+    assert_eq!(&s, "‘Hij zei “hallo”’");
+}
+
+#[test]
+fn dutch_apostrophes() {
+    let s = DutchFormatter::new()
+        .format("'s Avonds naar 's-Hertogenbosch, 't is 'n feest, zei 'Anna'.");
+    assert_eq!(&s, "’s Avonds naar ’s-Hertogenbosch, ’t is ’n feest, zei ‘Anna’.");
+
+    let s = DutchFormatter::new()
+        .typographic_quotes(false)
+        .format("'s avonds");
+    assert_eq!(&s, "'s avonds");
+}
@@ -0,0 +1,219 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::default::Default;
+
+use crate::common::{NB_CHAR, NB_CHAR_EM};
+use crate::common::{is_dash, dash_end, skip_whitespaces, is_whitespace};
+use crate::common::Pipeline;
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used in russian and ukrainian: «…» and „…“
+const EAST_SLAVIC_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('«', '»'),
+    secondary: ('„', '“'),
+    tertiary: Some(('‚', '‘')),
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Language used by `EastSlavicFormatter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EastSlavicLanguage {
+    /// Russian (ru)
+    Russian,
+    /// Ukrainian (uk)
+    Ukrainian,
+}
+
+impl EastSlavicLanguage {
+    /// Returns the abbreviations for "year" and "years", which are preceded by a
+    /// non-breaking space
+    fn year_abbreviations(self) -> &'static [&'static str] {
+        match self {
+            EastSlavicLanguage::Russian => &["г.", "гг."],
+            EastSlavicLanguage::Ukrainian => &["р.", "рр."],
+        }
+    }
+}
+
+/// Russian and ukrainian typographic formatter.
+///
+/// The purpose of this struct is to try to make a text more typographically correct,
+/// according to russian and ukrainian typographic rules. This means:
+///
+/// * replacing straight quotes with `«…»`, and `„…“` for nested quotes;
+/// * replacing spaced hyphens (or en dashes) between words with em dashes, with
+///   a non-breaking space before them (`Москва — столица`);
+/// * making space after `—` for dialog a demi em space, as `FrenchFormatter` does;
+/// * making spaces after initials (`А. С. Пушкин`) and before the abbreviations
+///   for years (`1799 г.`, `1990–1995 гг.`) non-breaking.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::EastSlavicFormatter;
+/// let output = EastSlavicFormatter::new()
+///              .format_tex("Москва - столица России, сказал \"А. С. Пушкин\" в 1830 г.");
+/// assert_eq!(&output, "Москва~— столица России, сказал «А.~С.~Пушкин» в 1830~г.");
+/// ```
+#[derive(Debug)]
+pub struct EastSlavicFormatter {
+    /// Russian or ukrainian
+    language: EastSlavicLanguage,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
+}
+
+impl Default for EastSlavicFormatter {
+    fn default() -> Self {
+        EastSlavicFormatter {
+            language: EastSlavicLanguage::Russian,
+            pipeline: Pipeline::default(),
+        }
+    }
+}
+
+impl EastSlavicFormatter {
+    /// Create a new EastSlavicFormatter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the language (russian or ukrainian).
+    ///
+    /// Default is `EastSlavicLanguage::Russian`.
+    pub fn language(&mut self, language: EastSlavicLanguage) -> &mut Self {
+        self.language = language;
+        self
+    }
+
+    /// (Try to) Format a string according to russian or ukrainian typographic rules.
+    ///
+    /// This method should be called for each paragraph, as it makes some suppositions that
+    /// the beginning of the string also means the beginning of a line.
+    pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        let input = self.pipeline.run(input.into(), &EAST_SLAVIC_QUOTES);
+
+        // No need to do anything, return early
+        if !input.contains(|c: char| c == '.' || is_dash(c)) {
+            return input;
+        }
+
+        let mut chars = format_dashes(&input.chars().collect::<Vec<_>>());
+
+        for i in 1..chars.len() {
+            if !is_whitespace(chars[i]) {
+                continue;
+            }
+            if is_initial(&chars, i) && chars[i + 1].is_uppercase() {
+                // Space after an initial
+                chars[i] = NB_CHAR;
+            } else if chars[i - 1].is_alphanumeric() {
+                // Space before abbreviation for year(s)
+                for abbr in self.language.year_abbreviations() {
+                    let len = abbr.chars().count();
+                    if chars.len() > i + len
+                        && chars[i + 1..=i + len].iter().copied().eq(abbr.chars())
+                        && (i + len + 1 == chars.len() || !chars[i + len + 1].is_alphanumeric()) {
+                            chars[i] = NB_CHAR;
+                        }
+                }
+            }
+        }
+
+        Cow::Owned(chars.into_iter().collect())
+    }
+}
+
+typographic_formatter!(EastSlavicFormatter, [tex, html, typst]);
+
+/// Returns true if the whitespace at index `n` follows an initial (e.g. `А.`)
+fn is_initial(v: &[char], n: usize) -> bool {
+    n >= 2 && n + 1 < v.len()
+        && v[n - 1] == '.'
+        && v[n - 2].is_uppercase()
+        && (n == 2 || !v[n - 3].is_alphanumeric())
+}
+
+/// Replace dialogue dashes and spaced dashes with em dashes, using the appropriate
+/// non-breaking spaces
+fn format_dashes(v: &[char]) -> Vec<char> {
+    let mut res = Vec::with_capacity(v.len());
+    let mut i = skip_whitespaces(v, 0);
+    res.extend_from_slice(&v[0..i]);
+
+    // Dash at the beginning of the paragraph: dialogue
+    if let Some(end) = dash_end(v, i) {
+        if end < v.len() && is_whitespace(v[end]) {
+            res.push('—');
+            res.push(NB_CHAR_EM);
+            i = skip_whitespaces(v, end);
+        }
+    }
+
+    while i < v.len() {
+        if i >= 2 && is_whitespace(v[i - 1]) && !is_whitespace(v[i - 2]) {
+            if let Some(end) = dash_end(v, i) {
+                let is_range = v[i - 2].is_ascii_digit()
+                    && v.get(end + 1).is_some_and(|c| c.is_ascii_digit());
+                if end < v.len() && is_whitespace(v[end]) && !is_range {
+                    // Spaced dash: em dash with non-breaking space before
+                    res.pop();
+                    res.push(NB_CHAR);
+                    res.push('—');
+                    i = end;
+                    continue;
+                }
+            }
+        }
+        res.push(v[i]);
+        i += 1;
+    }
+    res
+}
+
+
+#[test]
+fn russian_dashes() {
+    let f = EastSlavicFormatter::new();
+
+    let s = f.format_tex("Москва -- столица, а 5 - 6 не тире.");
+    assert_eq!(&s, "Москва~— столица, а 5 - 6 не тире.");
+
+    let s = f.format_tex("Северо-запад");
+    assert_eq!(&s, "Северо-запад");
+}
+
+#[test]
+fn russian_dialogue() {
+    let s = EastSlavicFormatter::new()
+        .format_tex("- Привет, - сказал он, - как дела?");
+    assert_eq!(&s, "—\\enspace Привет,~— сказал он,~— как дела?");
+}
+
+#[test]
+fn russian_quotes() {
+    let s = EastSlavicFormatter::new().format("\"Он сказал \"нет\"\"");
+    assert_eq!(&s, "«Он сказал „нет“»");
+}
+
+#[test]
+fn russian_initials_years() {
+    let f = EastSlavicFormatter::new();
+
+    let s = f.format_tex("Пушкин родился в 1799 г., а в 1820-х гг. уехал.");
+    assert_eq!(&s, "Пушкин родился в 1799~г., а в 1820-х~гг. уехал.");
+}
+
+#[test]
+fn ukrainian_years() {
+    let s = EastSlavicFormatter::new()
+        .language(EastSlavicLanguage::Ukrainian)
+        .format_tex("Т. Г. Шевченко народився 1814 р.");
+    assert_eq!(&s, "Т.~Г.~Шевченко народився 1814~р.");
+}
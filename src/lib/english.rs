@@ -0,0 +1,369 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+
+use crate::common::{NB_CHAR, dash_end, is_whitespace, skip_whitespaces};
+use crate::common::Pipeline;
+use crate::clean::{QuoteStyle, ENGLISH_QUOTES, ENGLISH_ELISIONS};
+
+/// Quotation marks used in british english: ‘…’ and “…”
+const BRITISH_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('‘', '’'),
+    secondary: ('“', '”'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: ENGLISH_ELISIONS,
+};
+
+/// Variant of english used by `EnglishFormatter`, which determines the quotation marks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnglishVariant {
+    /// American english: “…” and ‘…’ for nested quotes
+    American,
+    /// British english: ‘…’ and “…” for nested quotes
+    British,
+}
+
+/// Placement of commas and periods next to a closing quotation mark
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnglishPunctuation {
+    /// Leave them as they are
+    Unchanged,
+    /// Move them inside the closing quotation mark (`“word,”`)
+    American,
+    /// Move them outside the closing quotation mark (`“word”,`)
+    Logical,
+}
+
+/// Style of dashes used in the middle of a sentence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnglishDashes {
+    /// Leave them as they are
+    Unchanged,
+    /// Closed em dashes (`word—word`)
+    Closed,
+    /// Spaced en dashes (`word – word`), with a non-breaking space before the dash
+    Spaced,
+}
+
+/// English typographic formatter.
+///
+/// English typography doesn't require much more than the "generic" functions provided by
+/// the `clean` module, so by default this formatter simply applies them. This means:
+///
+/// * removing unnecessary whitespaces;
+/// * replacing straight quotes (`'` and `"`) with curly, typographic ones;
+/// * replacing ellipsis (`...`) with the unicode character (`…`).
+///
+/// It is also useful as a default, language-neutral implementation of `TypographicFormatter`.
+///
+/// Some options allow to enforce a house style:
+///
+/// * `variant` selects american (`“…”`) or british (`‘…’`) quotation marks;
+/// * `punctuation` moves commas and periods inside or outside closing quotation marks;
+/// * `dashes` uses closed em dashes or spaced en dashes in the middle of sentences.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::EnglishFormatter;
+/// let output = EnglishFormatter::new()
+///              .format("Some  'quoted' text...");
+/// assert_eq!(&output, "Some ‘quoted’ text…");
+/// ```
+#[derive(Debug)]
+pub struct EnglishFormatter {
+    /// American or british quotation marks
+    variant: EnglishVariant,
+    /// Placement of commas and periods next to closing quotes
+    punctuation: EnglishPunctuation,
+    /// Style of dashes
+    dashes: EnglishDashes,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
+}
+
+impl Default for EnglishFormatter {
+    fn default() -> Self {
+        EnglishFormatter {
+            variant: EnglishVariant::American,
+            punctuation: EnglishPunctuation::Unchanged,
+            dashes: EnglishDashes::Unchanged,
+            pipeline: Pipeline::default(),
+        }
+    }
+}
+
+impl EnglishFormatter {
+    /// Create a new EnglishFormatter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the variant (american or british), which determines the quotation marks.
+    ///
+    /// With `EnglishVariant::British`, double quotes in the source are replaced by `‘…’`
+    /// and single quotes by `“…”`, so the source text can use the same conventions for both
+    /// variants.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crowbook_text_processing::{EnglishFormatter, EnglishVariant};
+    /// let s = EnglishFormatter::new()
+    ///     .variant(EnglishVariant::British)
+    ///     .format("\"He said 'hi' to me\"");
+    /// assert_eq!(&s, "‘He said “hi” to me’");
+    /// ```
+    ///
+    /// Default is `EnglishVariant::American`.
+    pub fn variant(&mut self, variant: EnglishVariant) -> &mut Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Sets the placement of commas and periods next to closing quotation marks.
+    ///
+    /// Note that with `EnglishPunctuation::Logical`, periods are always moved outside, even
+    /// if they belong to a quoted sentence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crowbook_text_processing::{EnglishFormatter, EnglishPunctuation};
+    /// let s = EnglishFormatter::new()
+    ///     .punctuation(EnglishPunctuation::American)
+    ///     .format("A \"word\", then \"another\".");
+    /// assert_eq!(&s, "A “word,” then “another.”");
+    /// ```
+    ///
+    /// Default is `EnglishPunctuation::Unchanged`.
+    pub fn punctuation(&mut self, punctuation: EnglishPunctuation) -> &mut Self {
+        self.punctuation = punctuation;
+        self
+    }
+
+    /// Sets the style of dashes used in the middle of a sentence.
+    ///
+    /// Em dashes (`—`), double or triple hyphens (`--`, `---`) and spaced en dashes or
+    /// hyphens (` – `, ` - `) are recognized as dashes; hyphens inside words and numeric
+    /// ranges (`10 - 20`) are left alone.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crowbook_text_processing::{EnglishFormatter, EnglishDashes};
+    /// let s = EnglishFormatter::new()
+    ///     .dashes(EnglishDashes::Closed)
+    ///     .format("A well-known fact -- or not.");
+    /// assert_eq!(&s, "A well-known fact—or not.");
+    /// ```
+    ///
+    /// Default is `EnglishDashes::Unchanged`.
+    pub fn dashes(&mut self, dashes: EnglishDashes) -> &mut Self {
+        self.dashes = dashes;
+        self
+    }
+
+    /// (Try to) Format a string according to english typographic rules.
+    pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        let style = match self.variant {
+            EnglishVariant::American => &ENGLISH_QUOTES,
+            EnglishVariant::British => &BRITISH_QUOTES,
+        };
+        let input = self.pipeline.run(input.into(), style);
+
+        if self.punctuation == EnglishPunctuation::Unchanged
+            && self.dashes == EnglishDashes::Unchanged {
+            return input;
+        }
+
+        let mut chars = input.chars().collect::<Vec<_>>();
+        if self.punctuation != EnglishPunctuation::Unchanged {
+            move_punctuation(&mut chars, self.pipeline.quote_style(style),
+                             self.punctuation == EnglishPunctuation::American);
+        }
+        if self.dashes != EnglishDashes::Unchanged {
+            chars = format_dashes(&chars, self.dashes);
+        }
+
+        let output = chars.into_iter().collect::<String>();
+        if output == input {
+            input
+        } else {
+            Cow::Owned(output)
+        }
+    }
+}
+
+typographic_formatter!(EnglishFormatter, [tex, html, typst]);
+
+/// Returns, for each character, whether it is a closing quotation mark of `style`
+///
+/// A mark is only considered a closing quote if the corresponding opening mark has been
+/// opened, and, if it is the same character as the apostrophe, if it isn't followed by a letter.
+fn closing_quotes(v: &[char], style: &QuoteStyle) -> Vec<bool> {
+    let marks = [Some(style.primary), Some(style.secondary), style.tertiary];
+    // Closing marks of the currently opened quotes
+    let mut opened: Vec<char> = vec![];
+    let mut res = Vec::with_capacity(v.len());
+    for (i, c) in v.iter().enumerate() {
+        let is_apostrophe = *c == style.apostrophe
+            && v.get(i + 1).is_some_and(|c| c.is_alphanumeric());
+        let is_closing = match opened.iter().rposition(|close| close == c) {
+            Some(n) if !is_apostrophe => {
+                opened.truncate(n);
+                true
+            }
+            _ => {
+                if let Some((_, close)) = marks.iter().flatten().find(|(open, _)| open == c) {
+                    opened.push(*close);
+                }
+                false
+            }
+        };
+        res.push(is_closing);
+    }
+    res
+}
+
+/// Move commas and periods inside (if `inside` is true) or outside closing quotation marks
+fn move_punctuation(v: &mut [char], style: &QuoteStyle, inside: bool) {
+    if v.len() < 2 {
+        return;
+    }
+    let mut closing = closing_quotes(v, style);
+    let is_punctuation = |c: char| c == ',' || c == '.';
+    if inside {
+        // Go backward so the punctuation can move before several closing quotes
+        for i in (0..v.len() - 1).rev() {
+            if closing[i] && is_punctuation(v[i + 1]) {
+                v.swap(i, i + 1);
+                closing.swap(i, i + 1);
+            }
+        }
+    } else {
+        for i in 0..v.len() - 1 {
+            // Don't move an ellipsis or "..."
+            let is_ellipsis = i > 0 && v[i - 1] == '.';
+            if is_punctuation(v[i]) && closing[i + 1] && !is_ellipsis {
+                v.swap(i, i + 1);
+                closing.swap(i, i + 1);
+            }
+        }
+    }
+}
+
+/// Replace dashes in the middle of a sentence according to `style`
+fn format_dashes(v: &[char], style: EnglishDashes) -> Vec<char> {
+    let mut res: Vec<char> = Vec::with_capacity(v.len());
+    let mut i = 0;
+    while i < v.len() {
+        if let Some(end) = dash_end(v, i) {
+            let spaced_before = i > 0 && is_whitespace(v[i - 1]);
+            let spaced_after = end < v.len() && is_whitespace(v[end]);
+            let is_dash = match v[i] {
+                '—' => true,
+                '-' if end - i >= 2 => true,
+                // Single hyphen or en dash: only a dash if it is spaced
+                _ => spaced_before && spaced_after,
+            };
+            let next = skip_whitespaces(v, end);
+            let prev = if spaced_before { i.checked_sub(2) } else { i.checked_sub(1) };
+            let prev = prev.map(|j| v[j]);
+            let is_range = prev.is_some_and(|c| c.is_ascii_digit())
+                && v.get(next).is_some_and(|c| c.is_ascii_digit());
+            if is_dash && !is_range && next < v.len()
+                && prev.is_some_and(|c| !is_whitespace(c)) {
+                    if spaced_before {
+                        res.pop();
+                    }
+                    match style {
+                        EnglishDashes::Closed => res.push('—'),
+                        EnglishDashes::Spaced => {
+                            res.push(NB_CHAR);
+                            res.push('–');
+                            res.push(' ');
+                        }
+                        EnglishDashes::Unchanged => res.extend_from_slice(&v[i..next]),
+                    }
+                    i = next;
+                    continue;
+                }
+            res.extend_from_slice(&v[i..end]);
+            i = end;
+            continue;
+        }
+        res.push(v[i]);
+        i += 1;
+    }
+    res
+}
+
+
+#[test]
+fn english() {
+    let s = "  \"Hello,\"  she said. \"It's a  nice day...\"";
+    let res = EnglishFormatter::new().format(s);
+    assert_eq!(&res, " “Hello,” she said. “It’s a nice day…”");
+}
+
+#[test]
+fn english_options() {
+    let s = "Nothing -- 'really'...";
+    let res = EnglishFormatter::new()
+        .typographic_quotes(false)
+        .typographic_ellipsis(false)
+        .ligature_dashes(true)
+        .format(s);
+    assert_eq!(&res, "Nothing – 'really'...");
+}
+
+#[test]
+fn english_dyn() {
+    let mut f: Box<dyn crate::TypographicFormatter> = Box::new(EnglishFormatter::new());
+    f.typographic_quotes(false).ligature_guillemets(true);
+    let res = f.format_html("<< 'A' & B >>".into());
+    assert_eq!(&res, "« 'A' &amp; B »");
+}
+
+#[test]
+fn english_british() {
+    let s = EnglishFormatter::new()
+        .variant(EnglishVariant::British)
+        .format("\"It's 'fine'\", she said.");
+    assert_eq!(&s, "‘It’s “fine”’, she said.");
+}
+
+#[test]
+fn english_punctuation() {
+    let s = "‘He said “no”’, she said. “It’s ‘fine’”.";
+    let res = EnglishFormatter::new()
+        .punctuation(EnglishPunctuation::American)
+        .format(s);
+    assert_eq!(&res, "‘He said “no,”’ she said. “It’s ‘fine.’”");
+
+    let s = "“A ‘word,’” she said, “and the students’ books...” It’s “over.”";
+    let res = EnglishFormatter::new()
+        .punctuation(EnglishPunctuation::Logical)
+        .format(s);
+    assert_eq!(&res, "“A ‘word’”, she said, “and the students’ books…” It’s “over”.");
+}
+
+#[test]
+fn english_dashes() {
+    let s = "Well-known -- for 10 - 20 years --- and more - or less — really –";
+    let res = EnglishFormatter::new()
+        .dashes(EnglishDashes::Closed)
+        .format_tex(s);
+    assert_eq!(&res, "Well-known—for 10 - 20 years—and more—or less—really –");
+
+    let res = EnglishFormatter::new()
+        .dashes(EnglishDashes::Spaced)
+        .format_tex("Word—word -- and well-known.");
+    assert_eq!(&res, "Word~– word~– and well-known.");
+}
@@ -2,7 +2,8 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with
 // this file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-//! Some functions to escape character for display in HTML or LaTeX.
+//! Some functions to escape character for display in HTML, LaTeX, Markdown, Typst, roff or
+//! RTF.
 //!
 //! The two most useful ones are `tex` and `html`.
 //!
@@ -91,7 +92,30 @@ pub fn nnbsp<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
 /// assert_eq!(&s, "Des espaces insécables\\,? Ça alors\\,!");
 /// ```
 pub fn nb_spaces_tex<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    nb_spaces_tex_with(input, TexDialect::PdfLatex)
+}
+
+/// Escape non breaking spaces for the given TeX dialect.
+///
+/// With `TexDialect::UnicodeLatex`, the input is returned unchanged, since XeLaTeX and
+/// LuaLaTeX handle these characters correctly; else this is the same as `nb_spaces_tex`.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::escape;
+/// use crowbook_text_processing::escape::TexDialect;
+/// let s = escape::nb_spaces_tex_with("Ça alors\u{202F}!", TexDialect::UnicodeLatex);
+/// assert_eq!(&s, "Ça alors\u{202F}!");
+/// let s = escape::nb_spaces_tex_with("Ça alors\u{202F}!", TexDialect::Context);
+/// assert_eq!(&s, "Ça alors\\,!");
+/// ```
+pub fn nb_spaces_tex_with<'a, S: Into<Cow<'a, str>>>(input: S,
+                                                     dialect: TexDialect) -> Cow<'a, str> {
     let input = input.into();
+    if dialect == TexDialect::UnicodeLatex {
+        return input;
+    }
     if let Some(first) = input.chars().position(|c| match c {
         NB_CHAR | NB_CHAR_NARROW | NB_CHAR_EM => true,
         _ => false,
@@ -113,6 +137,69 @@ pub fn nb_spaces_tex<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
     }
 }
 
+/// Escape non breaking spaces for Typst: non-breaking spaces are replaced with `~`, and
+/// half-em spaces with `#h(0.5em);`. Narrow non-breaking spaces are kept as is, since Typst
+/// handles them correctly (and never breaks a line there), contrary to `h` spacing.
+///
+/// As for `nb_spaces_tex`, this function should always be called **after** `escape::typst`.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::escape;
+/// let s = escape::nb_spaces_typst("«\u{A0}Ça alors\u{202F}!\u{A0}»");
+/// assert_eq!(&s, "«~Ça alors\u{202F}!~»");
+/// ```
+pub fn nb_spaces_typst<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    let input = input.into();
+    if let Some(first) = input.find([NB_CHAR, NB_CHAR_EM]) {
+        let mut output = String::with_capacity(input.len() + input.len() / 8);
+        output.push_str(&input[0..first]);
+        for c in input[first..].chars() {
+            match c {
+                NB_CHAR_EM => output.push_str("#h(0.5em);"),
+                NB_CHAR => output.push('~'),
+                _ => output.push(c),
+            }
+        }
+        Cow::Owned(output)
+    } else {
+        input
+    }
+}
+
+/// Escape non breaking spaces for roff (groff, troff or mandoc): non-breaking spaces are
+/// replaced with `\ `, narrow non-breaking spaces with `\|` and half-em spaces with
+/// `\h'0.5m'`.
+///
+/// As for `nb_spaces_tex`, this function should always be called **after** `escape::roff`.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::escape;
+/// let s = escape::nb_spaces_roff("«\u{A0}Ça alors\u{202F}!\u{A0}»");
+/// assert_eq!(&s, r"«\ Ça alors\|!\ »");
+/// ```
+pub fn nb_spaces_roff<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    let input = input.into();
+    if let Some(first) = input.find([NB_CHAR, NB_CHAR_NARROW, NB_CHAR_EM]) {
+        let mut output = String::with_capacity(input.len() + input.len() / 8);
+        output.push_str(&input[0..first]);
+        for c in input[first..].chars() {
+            match c {
+                NB_CHAR_NARROW => output.push_str("\\|"),
+                NB_CHAR_EM => output.push_str("\\h'0.5m'"),
+                NB_CHAR => output.push_str("\\ "),
+                _ => output.push(c),
+            }
+        }
+        Cow::Owned(output)
+    } else {
+        input
+    }
+}
+
 /// Remove xml 1.0 invalid characters
 pub fn remove_xml_chars<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
     lazy_static! {
@@ -189,6 +276,21 @@ pub fn quotes<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
 }
 
 
+/// TeX engine and format that the output of `tex_with` and `nb_spaces_tex_with` is meant
+/// for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TexDialect {
+    /// pdfLaTeX, used by `tex` and `nb_spaces_tex`
+    #[default]
+    PdfLatex,
+    /// XeLaTeX or LuaLaTeX with fontspec: unicode characters (including non-breaking spaces)
+    /// are kept, and so are `<` and `>`. `!` is still escaped, since fontspec's
+    /// `Ligatures=TeX` turns ``!` `` into `¡`.
+    UnicodeLatex,
+    /// ConTeXt: special characters are replaced with `\letterhash{}`, `\lettertilde{}`, etc.
+    Context,
+}
+
 /// Escape characters for LaTeX
 ///
 /// # Example
@@ -199,6 +301,22 @@ pub fn quotes<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
 /// assert_eq!(&s, r"command -{}-foo \# calls command with option foo");
 /// ```
 pub fn tex<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    tex_with(input, TexDialect::PdfLatex)
+}
+
+/// Escape characters for the given TeX dialect
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::escape;
+/// use crowbook_text_processing::escape::TexDialect;
+/// let s = escape::tex_with("#1 <3 ~ 100%!", TexDialect::UnicodeLatex);
+/// assert_eq!(&s, r"\#1 <3 \textasciitilde{} 100\%!{}");
+/// let s = escape::tex_with("#1 <3 ~ 100%!", TexDialect::Context);
+/// assert_eq!(&s, r"\letterhash{}1 <3 \lettertilde{} 100\letterpercent{}!");
+/// ```
+pub fn tex_with<'a, S: Into<Cow<'a, str>>>(input: S, dialect: TexDialect) -> Cow<'a, str> {
     let input = input.into();
     const REGEX_LITERAL: &'static str = r"[!<>&%$#_\x7E\x2D\{\}\[\]\^\\]";
     lazy_static! {
@@ -217,6 +335,33 @@ pub fn tex<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
         for win in bytes.windows(2) {
             let c = win[0];
             let next = win[1];
+            match (dialect, c) {
+                (TexDialect::UnicodeLatex, b'<' | b'>')
+                    | (TexDialect::Context, b'<' | b'>' | b'!') => {
+                        output.push(c);
+                        continue;
+                    }
+                (TexDialect::Context, _) => {
+                    let escaped: &[u8] = match c {
+                        b'&' => br"\letterampersand{}",
+                        b'%' => br"\letterpercent{}",
+                        b'$' => br"\letterdollar{}",
+                        b'#' => br"\letterhash{}",
+                        b'_' => br"\letterunderscore{}",
+                        b'{' => br"\letterleftbrace{}",
+                        b'}' => br"\letterrightbrace{}",
+                        b'~' => br"\lettertilde{}",
+                        b'^' => br"\letterhat{}",
+                        b'\\' => br"\letterbackslash{}",
+                        _ => &[],
+                    };
+                    if !escaped.is_empty() {
+                        output.extend_from_slice(escaped);
+                        continue;
+                    }
+                }
+                _ => (),
+            }
             match c {
                 b'-' => {
                     if next == b'-' {
@@ -250,6 +395,262 @@ pub fn tex<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
     }
 }
 
+/// Escape characters for (CommonMark) Markdown, so that text is displayed as is.
+///
+/// This escapes the characters that have a meaning inside a paragraph (see
+/// `markdown_inline`), and the markers that have a meaning at the start of a line: `#`
+/// (headings), `>` (blockquotes), `-`, `+` and `=` (lists, thematic breaks and setext
+/// headings) and `1.` or `1)` (ordered lists).
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::escape;
+/// let s = escape::markdown("# 1. Some *text*\n2. with [a link](url)");
+/// assert_eq!(&s, "\\# 1. Some \\*text\\*\n2\\. with \\[a link\\](url)");
+/// ```
+pub fn markdown<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    lazy_static! {
+        static ref LINE_START: Regex =
+            Regex::new(r"(?m)^ {0,3}(?:[#>]|[-+=](?:[-+= \t]|$)|[0-9]{1,9}[.)](?:[ \t]|$))")
+            .unwrap();
+    }
+    let input = input.into();
+    if !MARKDOWN_INLINE.is_match(&input) && !LINE_START.is_match(&input) {
+        return input;
+    }
+    let mut output = String::with_capacity(input.len() + input.len() / 8);
+    for line in input.split_inclusive('\n') {
+        if let Some(mat) = LINE_START.find(line) {
+            // Escape the first char of the marker, or the `.` or `)` of a list number
+            let marker = mat.as_str().trim_start_matches(' ');
+            let n = mat.end() - marker.len()
+                + marker.find(|c: char| !c.is_ascii_digit()).unwrap();
+            output.push_str(&line[..n]);
+            output.push('\\');
+            push_markdown_inline(&mut output, &line[n..]);
+        } else {
+            push_markdown_inline(&mut output, line);
+        }
+    }
+    Cow::Owned(output)
+}
+
+/// Escape the characters that have a meaning inside a paragraph in (CommonMark) Markdown:
+/// `\`, `` ` ``, `*`, `_`, `[`, `]`, `<`, `&`, `|` and `~`.
+///
+/// Contrary to `markdown`, this doesn't escape markers at the start of lines, so it can be
+/// used for text inserted inside a line, e.g. in a link text or a table cell.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::escape;
+/// let s = escape::markdown_inline("# A | B_C");
+/// assert_eq!(&s, "# A \\| B\\_C");
+/// ```
+pub fn markdown_inline<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    let input = input.into();
+    let first = MARKDOWN_INLINE.find(&input)
+        .map(|mat| mat.start());
+    if let Some(first) = first {
+        let mut output = String::with_capacity(input.len() + input.len() / 8);
+        output.push_str(&input[0..first]);
+        push_markdown_inline(&mut output, &input[first..]);
+        Cow::Owned(output)
+    } else {
+        input
+    }
+}
+
+lazy_static! {
+    static ref MARKDOWN_INLINE: Regex = Regex::new(r"[\\`*_\[\]<&|~]").unwrap();
+}
+
+/// Push `s` to `output`, escaping the characters that have a meaning inside a paragraph
+fn push_markdown_inline(output: &mut String, s: &str) {
+    for c in s.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '&' | '|' | '~') {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+}
+
+/// Escape characters for Typst markup.
+///
+/// This escapes the characters that always have a meaning (`\`, `#`, `$`, `*`, `_`, `` ` ``,
+/// `<`, `@`, `~`, `[` and `]`), the sequences starting comments (`//` and `/*`) or ligatures
+/// (`--`), and the markers that have a meaning at the start of a line: `=` (headings), `-`,
+/// `+` and `/` (lists) and `1.` (numbered lists).
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::escape;
+/// let s = escape::typst("= #1 at 20$ -- see <intro> or @bob // TODO");
+/// assert_eq!(&s, r"\= \#1 at 20\$ \-- see \<intro> or \@bob /\/ TODO");
+/// ```
+pub fn typst<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    lazy_static! {
+        static ref REGEX: Regex = Regex::new(r"[\\#$*_`<@~\[\]]|--|/[/*]").unwrap();
+        static ref LINE_START: Regex =
+            Regex::new(r"(?m)^[ \t]*(?:=|[-+/](?:[ \t]|$)|[0-9]+\.(?:[ \t]|$))").unwrap();
+    }
+    let input = input.into();
+    if !REGEX.is_match(&input) && !LINE_START.is_match(&input) {
+        return input;
+    }
+    let mut output = String::with_capacity(input.len() + input.len() / 8);
+    for line in input.split_inclusive('\n') {
+        let mut chars = line.chars().peekable();
+        if let Some(mat) = LINE_START.find(line) {
+            // Escape the first char of the marker, or the `.` of a list number
+            let marker = mat.as_str().trim_start();
+            let n = mat.end() - marker.len()
+                + marker.find(|c: char| !c.is_ascii_digit()).unwrap();
+            output.push_str(&line[..n]);
+            output.push('\\');
+            output.push_str(&line[n..n + 1]);
+            chars = line[n + 1..].chars().peekable();
+        }
+        while let Some(c) = chars.next() {
+            let next = chars.peek().copied();
+            match (c, next) {
+                ('\\' | '#' | '$' | '*' | '_' | '`' | '<' | '@' | '~' | '[' | ']', _)
+                    | ('-', Some('-')) => output.push('\\'),
+                ('/', Some('/' | '*')) => {
+                    output.push(c);
+                    output.push('\\');
+                    output.push(chars.next().unwrap());
+                    continue;
+                }
+                _ => (),
+            }
+            output.push(c);
+        }
+    }
+    Cow::Owned(output)
+}
+
+/// Escape characters for roff (groff, troff or mandoc), e.g. for man pages.
+///
+/// Backslashes are replaced with `\e`, and `\&` is inserted before a `.` or a `'` at the start
+/// of a line so it isn't read as a request. Hyphens inside words are replaced with `\(hy`, and
+/// other ones (e.g. in command-line options or negative numbers) with `\-`. Typographic
+/// characters are replaced with groff glyphs (e.g. `\(em` for `—` or `\(lq` for `“`).
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::escape;
+/// let s = escape::roff(".. use “--foo” for a well-known C:\\ path…");
+/// assert_eq!(&s, r"\&.. use \(lq\-\-foo\(rq for a well\(hyknown C:\e path\[u2026]");
+/// ```
+pub fn roff<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    lazy_static! {
+        static ref REGEX: Regex =
+            Regex::new("(?m)^[.']|[-\\\\…–—“”‘’«»‹›„‚\u{2010}\u{2212}]").unwrap();
+    }
+    let input = input.into();
+    let first = REGEX.find(&input)
+        .map(|mat| mat.start());
+    if let Some(first) = first {
+        // Move back to the start of the line, to check for a leading `.` or `'`
+        let first = input[0..first].rfind('\n').map_or(0, |n| n + 1);
+        let mut output = String::with_capacity(input.len() + input.len() / 8);
+        output.push_str(&input[0..first]);
+        let chars = input[first..].chars().collect::<Vec<_>>();
+        for (i, &c) in chars.iter().enumerate() {
+            let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+            match c {
+                '.' | '\'' if prev.is_none_or(|c| c == '\n') => output.push_str("\\&"),
+                '\\' => {
+                    output.push_str("\\e");
+                    continue;
+                }
+                '-' if prev.is_some_and(char::is_alphabetic)
+                    && chars.get(i + 1).is_some_and(|c| c.is_alphabetic()) => {
+                        output.push_str("\\(hy");
+                        continue;
+                    }
+                '-' | '\u{2212}' => {
+                    output.push_str("\\-");
+                    continue;
+                }
+                _ => (),
+            }
+            let glyph = match c {
+                '\u{2010}' => "\\(hy",
+                '–' => "\\(en",
+                '—' => "\\(em",
+                '…' => "\\[u2026]",
+                '“' => "\\(lq",
+                '”' => "\\(rq",
+                '‘' => "\\(oq",
+                '’' => "\\(cq",
+                '«' => "\\(Fo",
+                '»' => "\\(Fc",
+                '‹' => "\\(fo",
+                '›' => "\\(fc",
+                '„' => "\\(Bq",
+                '‚' => "\\(bq",
+                _ => {
+                    output.push(c);
+                    continue;
+                }
+            };
+            output.push_str(glyph);
+        }
+        Cow::Owned(output)
+    } else {
+        input
+    }
+}
+
+/// Escape characters for RTF.
+///
+/// Backslashes and braces are escaped, non-breaking spaces are replaced with `\~`, and other
+/// non-ASCII characters (including narrow non-breaking spaces) are replaced with `\uN?`,
+/// where `N` is the signed 16-bit value of each UTF-16 code unit of the character (so
+/// characters outside the BMP use two of them), and `?` is the replacement for readers
+/// that don't support Unicode.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::escape;
+/// let s = escape::rtf("{Ça}\\ va\u{A0}?\u{202F}😀");
+/// assert_eq!(&s, r"\{\u199?a\}\\ va\~?\u8239?\u-10179?\u-8704?");
+/// ```
+pub fn rtf<'a, S: Into<Cow<'a, str>>>(input: S) -> Cow<'a, str> {
+    let input = input.into();
+    let first = input.find(|c: char| !c.is_ascii() || matches!(c, '\\' | '{' | '}'));
+    if let Some(first) = first {
+        let mut output = String::with_capacity(input.len() + input.len() / 2);
+        output.push_str(&input[0..first]);
+        for c in input[first..].chars() {
+            match c {
+                '\\' | '{' | '}' => {
+                    output.push('\\');
+                    output.push(c);
+                }
+                NB_CHAR => output.push_str("\\~"),
+                _ if c.is_ascii() => output.push(c),
+                _ => {
+                    let mut buffer = [0; 2];
+                    for unit in c.encode_utf16(&mut buffer) {
+                        output.push_str(&format!("\\u{}?", *unit as i16));
+                    }
+                }
+            }
+        }
+        Cow::Owned(output)
+    } else {
+        input
+    }
+}
+
 
 #[test]
 fn html_0() {
@@ -357,3 +758,88 @@ fn xml_chars() {
     let expected = "Hey";
     assert_eq!(&actual, expected);
 }
+
+#[test]
+fn markdown_0() {
+    let s = "Some string without any character to escape";
+    let result = markdown(s);
+    assert!(matches!(result, Cow::Borrowed(_)));
+    assert_eq!(s, &result);
+    let result = markdown_inline(s);
+    assert!(matches!(result, Cow::Borrowed(_)));
+}
+
+#[test]
+fn markdown_1() {
+    let actual = markdown("> quote\n  - item\n---\n+1 and 2021. Or\n1999) `code` & <b>\n-5");
+    let expected = "\\> quote\n  \\- item\n\\---\n+1 and 2021. Or\n1999\\) \\`code\\` \\& \\<b>\n-5";
+    assert_eq!(&actual, expected);
+    let actual = markdown_inline(r"C:\dir\*.txt ~~not struck~~");
+    let expected = r"C:\\dir\\\*.txt \~\~not struck\~\~";
+    assert_eq!(&actual, expected);
+}
+
+#[test]
+fn typst_0() {
+    let s = "Some string without any character to escape";
+    let result = typst(s);
+    assert_eq!(s, &result);
+    let result = nb_spaces_typst(s);
+    assert_eq!(s, &result);
+}
+
+#[test]
+fn typst_1() {
+    let actual = typst("- *not* a list\n12. neither\n/* or */ a_b [c] ~ `d` \\e");
+    let expected = "\\- \\*not\\* a list\n12\\. neither\n/\\* or \\*/ a\\_b \\[c\\] \\~ \\`d\\` \\\\e";
+    assert_eq!(&actual, expected);
+    let actual = nb_spaces_typst("— Oui\u{2002}— non\u{A0}!");
+    assert_eq!(&actual, "— Oui#h(0.5em);— non~!");
+}
+
+#[test]
+fn roff_0() {
+    let s = "Some string without any character to escape";
+    let result = roff(s);
+    assert_eq!(s, &result);
+    let result = nb_spaces_roff(s);
+    assert_eq!(s, &result);
+}
+
+#[test]
+fn roff_1() {
+    let actual = roff("'Tis a line\n.TH not a macro\n-5 — ‘it’s’ «x-ray»");
+    let expected = r"\&'Tis a line
+\&.TH not a macro
+\-5 \(em \(oqit\(cqs\(cq \(Fox\(hyray\(Fc";
+    assert_eq!(&actual, expected);
+    let actual = nb_spaces_roff(roff("— Oui\u{2002}— 10\u{202F}000\u{A0}€"));
+    assert_eq!(&actual, r"\(em Oui\h'0.5m'\(em 10\|000\ €");
+}
+
+#[test]
+fn rtf_0() {
+    let s = "Some string without any character to escape";
+    let result = rtf(s);
+    assert_eq!(s, &result);
+}
+
+#[test]
+fn rtf_1() {
+    let actual = rtf("«\u{202F}Été\u{A0}» — 𝄞 \\par");
+    let expected = r"\u171?\u8239?\u201?t\u233?\~\u187? \u8212? \u-10188?\u-8930? \\par";
+    assert_eq!(&actual, expected);
+}
+
+#[test]
+fn tex_dialects() {
+    let s = r"\emph{x_1} -- #2 & 3 > 2!";
+    assert_eq!(&tex_with(s, TexDialect::PdfLatex), &tex(s));
+    assert_eq!(&tex_with(s, TexDialect::UnicodeLatex),
+               r"\textbackslash{}emph\{x\_1\} -{}- \#2 \& 3 > 2!{}");
+    assert_eq!(&tex_with(s, TexDialect::Context),
+               r"\letterbackslash{}emph\letterleftbrace{}x\letterunderscore{}1\letterrightbrace{} -{}- \letterhash{}2 \letterampersand{} 3 > 2!");
+    let s = "— Oui\u{2002}! 10\u{202F}000\u{A0}€";
+    assert_eq!(&nb_spaces_tex_with(s, TexDialect::Context), "— Oui\\enspace ! 10\\,000~€");
+    assert_eq!(&nb_spaces_tex_with(s, TexDialect::UnicodeLatex), s);
+}
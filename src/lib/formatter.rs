@@ -0,0 +1,459 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+use crate::escape;
+use crate::clean::QuoteStyle;
+use crate::{FrenchFormatter, FrenchRegion, EnglishFormatter, EnglishVariant, GermanFormatter,
+            SpanishFormatter, ItalianFormatter, PortugueseFormatter, PortugueseVariant,
+            WestSlavicFormatter, WestSlavicLanguage, EastSlavicFormatter, EastSlavicLanguage,
+            NordicFormatter, NordicLanguage, CjkFormatter, CjkLanguage, DutchFormatter,
+            GreekFormatter, HungarianFormatter, CatalanFormatter, RtlFormatter, RtlLanguage};
+
+/// Common interface of the language-specific typographic formatters.
+///
+/// Each formatter (e.g. `FrenchFormatter` or `EnglishFormatter`) also provides these methods
+/// directly, with a more convenient signature; this trait is useful when the formatter must be
+/// chosen at runtime, e.g. according to the language of a book, and stored as a
+/// `Box<dyn TypographicFormatter>`.
+///
+/// Besides their own rules, all formatters apply "generic" steps: unnecessary whitespaces are
+/// removed first (see `clean::whitespaces`), then straight quotes and ellipsis are replaced
+/// and, if enabled, dashes and guillemets ligatures.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::{TypographicFormatter, FrenchFormatter, EnglishFormatter};
+///
+/// let lang = "fr";
+/// let mut formatter: Box<dyn TypographicFormatter> = if lang == "fr" {
+///     Box::new(FrenchFormatter::new())
+/// } else {
+///     Box::new(EnglishFormatter::new())
+/// };
+/// formatter.typographic_ellipsis(false);
+/// let s = formatter.format_tex("Un 'test'...".into());
+/// assert_eq!(&s, "Un ‘test’...");
+/// ```
+pub trait TypographicFormatter: Debug {
+    /// (Try to) Format a string according to the typographic rules of the language.
+    ///
+    /// This method should be called for each paragraph, as it can make the supposition that
+    /// the beginning of the string also means the beginning of a line.
+    fn format<'a>(&self, input: Cow<'a, str>) -> Cow<'a, str>;
+
+    /// Format a string, escape the characters that need to be escaped in LaTeX and use TeX
+    /// commands for non-breaking spaces. See `escape::tex` and `escape::nb_spaces_tex`.
+    fn format_tex<'a>(&self, input: Cow<'a, str>) -> Cow<'a, str> {
+        escape::nb_spaces_tex(escape::tex(self.format(input)))
+    }
+
+    /// Format a string, escape the characters that need to be escaped in Typst and use `~` or
+    /// `#h()` for non-breaking spaces. See `escape::typst` and `escape::nb_spaces_typst`.
+    fn format_typst<'a>(&self, input: Cow<'a, str>) -> Cow<'a, str> {
+        escape::nb_spaces_typst(escape::typst(self.format(input)))
+    }
+
+    /// Format a string, escape the characters that need to be escaped in HTML and use HTML
+    /// elements for narrow non-breaking spaces. See `escape::html` and `escape::nb_spaces_html`.
+    fn format_html<'a>(&self, input: Cow<'a, str>) -> Cow<'a, str> {
+        escape::nb_spaces_html(escape::html(self.format(input)))
+    }
+
+    /// Enables the typographic quotes replacement.
+    fn typographic_quotes(&mut self, b: bool) -> &mut dyn TypographicFormatter;
+
+    /// Sets the quotation marks replacing straight quotes, instead of the ones of the language.
+    fn quote_style(&mut self, style: QuoteStyle) -> &mut dyn TypographicFormatter;
+
+    /// Adds elided words whose straight quotes are always apostrophes (e.g. `'til`).
+    fn add_elisions(&mut self, words: &[&str]) -> &mut dyn TypographicFormatter;
+
+    /// Enables typographic ellipsis replacement.
+    fn typographic_ellipsis(&mut self, b: bool) -> &mut dyn TypographicFormatter;
+
+    /// If set to true, replaces `--`to `–` and `---` to `—`.
+    fn ligature_dashes(&mut self, b: bool) -> &mut dyn TypographicFormatter;
+
+    /// If set to true, replaces `<<` to `«` and `>>` to `»`.
+    fn ligature_guillemets(&mut self, b: bool) -> &mut dyn TypographicFormatter;
+}
+
+/// Implements, for a formatter whose generic steps are handled by a `pipeline: Pipeline`
+/// field, the setters of these steps, the escaping variants of `format` and
+/// `TypographicFormatter`.
+///
+/// Only the escaping variants listed in brackets (among `tex`, `html` and `typst`) are
+/// generated; the formatter must implement the other ones itself. If `numbers` is listed,
+/// the setters of the thresholds used by `Pipeline::numbers` are generated too.
+///
+/// Doc comments following `quote_style:` are appended to the documentation of the
+/// `quote_style` setter.
+macro_rules! typographic_formatter {
+    ($name:ident, [$($format:ident),*] $(, quote_style: $(#[$quote_style_doc:meta])+)?) => {
+        impl $name {
+            /// Enables the typographic quotes replacement.
+            ///
+            /// If true, "it's" will be replaced by "it’s"
+            ///
+            /// Default is true
+            pub fn typographic_quotes(&mut self, b: bool) -> &mut Self {
+                self.pipeline.typographic_quotes = b;
+                self
+            }
+
+            /// Sets the quotation marks replacing straight quotes, instead of the ones of the
+            /// language.
+            ///
+            /// See `clean::QuoteStyle`.
+            $($(#[$quote_style_doc])+)?
+            pub fn quote_style(&mut self, style: $crate::clean::QuoteStyle) -> &mut Self {
+                self.pipeline.quote_style = Some(style);
+                self
+            }
+
+            /// Adds elided words whose straight quotes are always apostrophes (e.g. `'til`),
+            /// in addition to the ones of the quotation marks.
+            ///
+            /// See `clean::quotes_with_elisions`.
+            pub fn add_elisions(&mut self, words: &[&str]) -> &mut Self {
+                self.pipeline.elisions.extend(words.iter().map(|w| w.to_string()));
+                self
+            }
+
+            /// Enables typographic ellipsis replacement.
+            ///
+            /// If true, "..." will be replaced by "…"
+            ///
+            /// Default is true
+            pub fn typographic_ellipsis(&mut self, b: bool) -> &mut Self {
+                self.pipeline.typographic_ellipsis = b;
+                self
+            }
+
+            /// If set to true, replaces `--`to `–` and `---` to `—`.
+            ///
+            /// Default is false.
+            pub fn ligature_dashes(&mut self, b: bool) -> &mut Self {
+                self.pipeline.ligature_dashes = b;
+                self
+            }
+
+            /// If set to true, replaces `<<` to `«` and `>>` to `»`.
+            ///
+            /// Default is false.
+            pub fn ligature_guillemets(&mut self, b: bool) -> &mut Self {
+                self.pipeline.ligature_guillemets = b;
+                self
+            }
+
+            $(typographic_formatter!(@format $format);)*
+        }
+
+        impl $crate::TypographicFormatter for $name {
+            fn format<'a>(&self, input: ::std::borrow::Cow<'a, str>)
+                          -> ::std::borrow::Cow<'a, str> {
+                $name::format(self, input)
+            }
+
+            fn format_tex<'a>(&self, input: ::std::borrow::Cow<'a, str>)
+                              -> ::std::borrow::Cow<'a, str> {
+                $name::format_tex(self, input)
+            }
+
+            fn format_html<'a>(&self, input: ::std::borrow::Cow<'a, str>)
+                               -> ::std::borrow::Cow<'a, str> {
+                $name::format_html(self, input)
+            }
+
+            fn format_typst<'a>(&self, input: ::std::borrow::Cow<'a, str>)
+                                -> ::std::borrow::Cow<'a, str> {
+                $name::format_typst(self, input)
+            }
+
+            fn typographic_quotes(&mut self, b: bool) -> &mut dyn $crate::TypographicFormatter {
+                $name::typographic_quotes(self, b)
+            }
+
+            fn quote_style(&mut self, style: $crate::clean::QuoteStyle)
+                           -> &mut dyn $crate::TypographicFormatter {
+                $name::quote_style(self, style)
+            }
+
+            fn add_elisions(&mut self, words: &[&str]) -> &mut dyn $crate::TypographicFormatter {
+                $name::add_elisions(self, words)
+            }
+
+            fn typographic_ellipsis(&mut self, b: bool) -> &mut dyn $crate::TypographicFormatter {
+                $name::typographic_ellipsis(self, b)
+            }
+
+            fn ligature_dashes(&mut self, b: bool) -> &mut dyn $crate::TypographicFormatter {
+                $name::ligature_dashes(self, b)
+            }
+
+            fn ligature_guillemets(&mut self, b: bool) -> &mut dyn $crate::TypographicFormatter {
+                $name::ligature_guillemets(self, b)
+            }
+        }
+    };
+    (@format numbers) => {
+        /// Sets the threshold currency.
+        ///
+        /// After that number of characters, assume it's not a currency
+        ///
+        /// Default is `3`.
+        pub fn threshold_currency(&mut self, t: usize) -> &mut Self {
+            self.pipeline.threshold_currency = t;
+            self
+        }
+
+        /// Sets the threshold for unit.
+        ///
+        /// After that number of characters, assume it's not an unit.
+        ///
+        /// Default is `2`.
+        pub fn threshold_unit(&mut self, t: usize) -> &mut Self {
+            self.pipeline.threshold_unit = t;
+            self
+        }
+    };
+    (@format tex) => {
+        /// (Try to) Format a string, escape the characters that need to be escaped in LaTeX
+        /// (e.g. backslashes) and use TeX commands for non-breaking spaces. See `escape::tex`
+        /// and `escape::nb_spaces_tex`.
+        pub fn format_tex<'a, S>(&self, input: S) -> ::std::borrow::Cow<'a, str>
+        where S: Into<::std::borrow::Cow<'a, str>> {
+            $crate::escape::nb_spaces_tex($crate::escape::tex(self.format(input)))
+        }
+    };
+    (@format html) => {
+        /// (Try to) Format a string, escape the characters that need to be escaped in HTML
+        /// (e.g. &) and use HTML elements for narrow non-breaking spaces. See `escape::html`
+        /// and `escape::nb_spaces_html`.
+        pub fn format_html<'a, S>(&self, input: S) -> ::std::borrow::Cow<'a, str>
+        where S: Into<::std::borrow::Cow<'a, str>> {
+            $crate::escape::nb_spaces_html($crate::escape::html(self.format(input)))
+        }
+    };
+    (@format typst) => {
+        /// (Try to) Format a string, escape the characters that need to be escaped in Typst
+        /// (e.g. `#`) and use `~` and `#h()` for non-breaking spaces. See `escape::typst` and
+        /// `escape::nb_spaces_typst`.
+        pub fn format_typst<'a, S>(&self, input: S) -> ::std::borrow::Cow<'a, str>
+        where S: Into<::std::borrow::Cow<'a, str>> {
+            $crate::escape::nb_spaces_typst($crate::escape::typst(self.format(input)))
+        }
+    };
+}
+
+/// Defines a formatter whose only language-specific rules are its quotation marks, `$quotes`,
+/// and optional steps (functions taking and returning a `Cow<str>`) run after the generic
+/// ones. Spaces in numbers are made narrow and non-breaking.
+macro_rules! simple_formatter {
+    ($(#[$doc:meta])* $name:ident, $quotes:expr $(, $step:path)*) => {
+        $(#[$doc])*
+        #[derive(Debug, Default)]
+        pub struct $name {
+            /// Generic cleaning steps
+            pipeline: $crate::common::Pipeline,
+        }
+
+        impl $name {
+            /// Create a new formatter with default settings
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// (Try to) Format a string according to the typographic rules of the language.
+            pub fn format<'a, S>(&self, input: S) -> ::std::borrow::Cow<'a, str>
+            where S: Into<::std::borrow::Cow<'a, str>> {
+                let input = self.pipeline.run(input.into(), &$quotes);
+                $(let input = $step(input);)*
+                self.pipeline.numbers(input)
+            }
+        }
+
+        typographic_formatter!($name, [tex, html, typst, numbers]);
+    };
+}
+
+/// Language tags (BCP-47) for which a specific formatter exists
+const SUPPORTED_TAGS: &[&str] = &[
+    "ar",
+    "ca",
+    "cs",
+    "da",
+    "de",
+    "el",
+    "en", "en-GB", "en-US",
+    "es",
+    "fi",
+    "fr", "fr-BE", "fr-CA", "fr-CH", "fr-FR",
+    "he",
+    "hu",
+    "it",
+    "ja",
+    "ko",
+    "nb", "nl", "nn", "no",
+    "pl",
+    "pt", "pt-BR", "pt-PT",
+    "ru",
+    "sk",
+    "sv",
+    "uk",
+    "zh", "zh-CN", "zh-HK", "zh-Hans", "zh-Hant", "zh-MO", "zh-SG", "zh-TW",
+];
+
+/// Creates a new formatter and calls a setter on it
+macro_rules! configured {
+    ($formatter:ident, $setter:ident, $value:expr) => {{
+        let mut f = $formatter::new();
+        f.$setter($value);
+        Box::new(f)
+    }}
+}
+
+/// Returns the formatter corresponding exactly to a (lowercase) language tag
+fn formatter_for_exact_tag(tag: &str) -> Option<Box<dyn TypographicFormatter>> {
+    let formatter: Box<dyn TypographicFormatter> = match tag {
+        "en" | "en-us" => Box::new(EnglishFormatter::new()),
+        "en-gb" => configured!(EnglishFormatter, variant, EnglishVariant::British),
+        "fr" | "fr-fr" => Box::new(FrenchFormatter::new()),
+        "fr-be" => configured!(FrenchFormatter, region, FrenchRegion::Belgium),
+        "fr-ca" => configured!(FrenchFormatter, region, FrenchRegion::Canada),
+        "fr-ch" => configured!(FrenchFormatter, region, FrenchRegion::Switzerland),
+        "de" => Box::new(GermanFormatter::new()),
+        "es" => Box::new(SpanishFormatter::new()),
+        "it" => Box::new(ItalianFormatter::new()),
+        "pt" | "pt-br" => Box::new(PortugueseFormatter::new()),
+        "pt-pt" => configured!(PortugueseFormatter, variant, PortugueseVariant::European),
+        "pl" => Box::new(WestSlavicFormatter::new()),
+        "cs" => configured!(WestSlavicFormatter, language, WestSlavicLanguage::Czech),
+        "sk" => configured!(WestSlavicFormatter, language, WestSlavicLanguage::Slovak),
+        "ru" => Box::new(EastSlavicFormatter::new()),
+        "uk" => configured!(EastSlavicFormatter, language, EastSlavicLanguage::Ukrainian),
+        "sv" => Box::new(NordicFormatter::new()),
+        "no" | "nb" | "nn" => configured!(NordicFormatter, language, NordicLanguage::Norwegian),
+        "da" => configured!(NordicFormatter, language, NordicLanguage::Danish),
+        "fi" => configured!(NordicFormatter, language, NordicLanguage::Finnish),
+        "zh" | "zh-hans" | "zh-cn" | "zh-sg" => Box::new(CjkFormatter::new()),
+        "zh-hant" | "zh-tw" | "zh-hk" | "zh-mo" =>
+            configured!(CjkFormatter, language, CjkLanguage::TraditionalChinese),
+        "ja" => configured!(CjkFormatter, language, CjkLanguage::Japanese),
+        "ko" => configured!(CjkFormatter, language, CjkLanguage::Korean),
+        "nl" => Box::new(DutchFormatter::new()),
+        "el" => Box::new(GreekFormatter::new()),
+        "hu" => Box::new(HungarianFormatter::new()),
+        "ca" => Box::new(CatalanFormatter::new()),
+        "he" => Box::new(RtlFormatter::new()),
+        "ar" => configured!(RtlFormatter, language, RtlLanguage::Arabic),
+        _ => return None,
+    };
+    Some(formatter)
+}
+
+/// Returns the best matching formatter for a BCP-47 language tag (e.g. `"fr"` or `"fr-CA"`),
+/// or `None` if this language is not supported.
+///
+/// Tags are case-insensitive, and `_` is accepted as a separator. If there is no formatter
+/// specific to the script or region (e.g. `"de-CH"`), the formatter for the language is
+/// returned.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::formatter_for_lang;
+/// let formatter = formatter_for_lang("el-GR").unwrap();
+/// assert_eq!(&formatter.format("\"Γεια\"".into()), "«Γεια»");
+/// let formatter = formatter_for_lang("zh-Hant-TW").unwrap();
+/// assert_eq!(&formatter.format("\"你好\"".into()), "「你好」");
+/// assert!(formatter_for_lang("tlh").is_none());
+/// ```
+pub fn formatter_for_lang(tag: &str) -> Option<Box<dyn TypographicFormatter>> {
+    let tag = tag.trim().to_lowercase();
+    let mut subtags = tag.split(['-', '_']);
+    let lang = subtags.next().unwrap_or("");
+    subtags
+        .filter_map(|subtag| formatter_for_exact_tag(&format!("{}-{}", lang, subtag)))
+        .next()
+        .or_else(|| formatter_for_exact_tag(lang))
+}
+
+/// Returns the best matching formatter for a BCP-47 language tag, like `formatter_for_lang`,
+/// but falls back to a neutral formatter (which only applies the "generic" rules, see
+/// `EnglishFormatter`) if the language is not supported.
+///
+/// This allows to configure typography from the metadata of a document.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::get_formatter;
+/// let formatter = get_formatter("fr-CA");
+/// assert_eq!(&formatter.format_tex("Quoi ?".into()), "Quoi?");
+/// let formatter = get_formatter("tlh");
+/// assert_eq!(&formatter.format_tex("'Qapla'...".into()), "‘Qapla’…");
+/// ```
+pub fn get_formatter(tag: &str) -> Box<dyn TypographicFormatter> {
+    formatter_for_lang(tag).unwrap_or_else(|| Box::new(EnglishFormatter::new()))
+}
+
+/// Returns the list of language tags for which `formatter_for_lang` and `get_formatter`
+/// return a specific formatter.
+///
+/// Other tags with the same language (e.g. `"de-AT"`) are also supported, and use the
+/// formatter of this language.
+pub fn supported_languages() -> &'static [&'static str] {
+    SUPPORTED_TAGS
+}
+
+
+#[test]
+fn formatter_tags() {
+    for tag in supported_languages() {
+        assert!(formatter_for_exact_tag(&tag.to_lowercase()).is_some(), "{}", tag);
+    }
+}
+
+#[test]
+fn formatter_fallback() {
+    let s = get_formatter("fr_ch").format_tex("« Oui ? »".into());
+    assert_eq!(&s, "«\\,Oui\\,?\\,»");
+
+    let s = get_formatter("de-CH").format("\"Ja\"".into());
+    assert_eq!(&s, "„Ja“");
+
+    let s = get_formatter("en-GB").format("\"Yes\"".into());
+    assert_eq!(&s, "‘Yes’");
+
+    let s = get_formatter("pt-Latn-PT").format("\"Sim\"".into());
+    assert_eq!(&s, "«Sim»");
+
+    let s = get_formatter("").format("\"Yes\"".into());
+    assert_eq!(&s, "“Yes”");
+}
+
+#[test]
+fn formatter_quote_style() {
+    let mut f = get_formatter("de");
+    f.quote_style(QuoteStyle {
+        primary: ('«', '»'),
+        secondary: ('‹', '›'),
+        .. QuoteStyle::default()
+    });
+    let s = f.format("\"Er sagt 'ja'\"".into());
+    assert_eq!(&s, "«Er sagt ‹ja›»");
+}
+
+#[test]
+fn formatter_elisions() {
+    let mut f = get_formatter("en");
+    f.add_elisions(&["'ere", "'ave"]);
+    let s = f.format("'Ere, 'ave a look".into());
+    assert_eq!(&s, "’Ere, ’ave a look");
+}
@@ -6,11 +6,68 @@ use std::borrow::Cow;
 use std::default::Default;
 
 use crate::common::{NB_CHAR, NB_CHAR_NARROW, NB_CHAR_EM};
+use crate::common;
 use crate::common::is_whitespace;
-use crate::clean;
+use crate::common::Pipeline;
 use crate::escape;
+use crate::clean::QuoteStyle;
+use crate::escape::TexDialect;
 
 
+/// Quotation marks replacing straight quotes before french rules are applied: “…” and ‘…’.
+///
+/// Unlike english, no word is elided, so e.g. `'n'` is quoted.
+const FRENCH_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('“', '”'),
+    secondary: ('‘', '’'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Region whose typographic conventions are used by `FrenchFormatter`
+///
+/// The main difference is the spacing before punctuation marks and inside guillemets:
+///
+/// * `France` and `Belgium` use narrow non-breaking spaces before `?`, `!` and `;`, non-breaking
+///   spaces before `:`, and non-breaking or narrow non-breaking spaces inside guillemets,
+///   according to the circumstances (dialog or a few quoted words);
+/// * `Canada` (Québec) doesn't use spaces before `?`, `!` and `;`, and uses non-breaking spaces
+///   before `:` and inside guillemets;
+/// * `Switzerland` uses narrow non-breaking spaces before `?`, `!` and `;` and inside guillemets,
+///   and non-breaking spaces before `:`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrenchRegion {
+    /// fr-FR
+    France,
+    /// fr-CA
+    Canada,
+    /// fr-CH
+    Switzerland,
+    /// fr-BE
+    Belgium,
+}
+
+impl FrenchRegion {
+    /// Space before `?`, `!` and `;`, or `None` if there should be no space
+    fn space_before_punctuation(self) -> Option<char> {
+        match self {
+            FrenchRegion::Canada => None,
+            _ => Some(NB_CHAR_NARROW),
+        }
+    }
+
+    /// Space inside guillemets, or `None` if it depends on the length of the quote
+    fn space_inside_guillemets(self) -> Option<char> {
+        match self {
+            FrenchRegion::Canada => Some(NB_CHAR),
+            FrenchRegion::Switzerland => Some(NB_CHAR_NARROW),
+            FrenchRegion::France | FrenchRegion::Belgium => None,
+        }
+    }
+}
 
 /// French typographic formatter.
 ///
@@ -24,7 +81,7 @@ use crate::escape;
 ///   according to the circumstances (dialog or a few quoted words).
 /// * making spaces in numbers, e.g. `80 000` or `50 €` narrow and non-breaking.
 ///
-/// Additionally, this feature use functions that are "generic" (not specific to french language)
+/// Additionally, this feature uses functions that are "generic" (not specific to french language)
 /// in order to:
 ///
 /// * replace straight quotes (`'` and `"`) with curly, typographic ones;
@@ -33,6 +90,9 @@ use crate::escape;
 /// As some of these features require a bit of guessing sometimes, there are some paremeters that
 /// can be set if you want better results.
 ///
+/// These rules are the ones used in France; other regions can be selected with `region`
+/// (see `FrenchRegion`).
+///
 /// # Example
 ///
 /// ```
@@ -46,35 +106,26 @@ use crate::escape;
 /// ```
 #[derive(Debug)]
 pub struct FrenchFormatter {
-    /// After that number of characters, assume it's not a currency
-    threshold_currency: usize,
-    /// After that number of characters assume it's not an unit
-    threshold_unit: usize,
+    /// Region whose spacing rules are used
+    region: FrenchRegion,
+    /// TeX dialect used by `format_tex`
+    tex_dialect: TexDialect,
     /// After that number of characters, assume it is a dialog
     threshold_quote: usize,
     /// After that number of characters, assume it isn't an abbreviation
     threshold_real_word: usize,
-    /// Enable typographic apostrophe
-    typographic_quotes: bool,
-    /// Enaple typographic ellipsis
-    typographic_ellipsis: bool,
-    /// Enable dashes replacement
-    ligature_dashes: bool,
-    /// Enable guillemets replacement
-    ligature_guillemets: bool,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
 }
 
 impl Default for FrenchFormatter {
     fn default() -> Self {
         FrenchFormatter {
-            threshold_currency: 3,
-            threshold_unit: 2,
+            region: FrenchRegion::France,
+            tex_dialect: TexDialect::PdfLatex,
             threshold_quote: 20,
             threshold_real_word: 3,
-            typographic_quotes: true,
-            typographic_ellipsis: true,
-            ligature_dashes: false,
-            ligature_guillemets: false,
+            pipeline: Pipeline::default(),
         }
     }
 }
@@ -85,23 +136,41 @@ impl FrenchFormatter {
         Self::default()
     }
 
-    /// Sets the threshold currency.
+    /// Sets the region, whose conventions for spacing before punctuation marks and
+    /// inside guillemets differ.
     ///
-    /// After that number of characters, assume it's not a currency
+    /// # Example
     ///
-    /// Default is `3`.
-    pub fn threshold_currency(&mut self, t: usize) -> &mut Self {
-        self.threshold_currency = t;
+    /// ```
+    /// use crowbook_text_processing::{FrenchFormatter, FrenchRegion};
+    /// let s = FrenchFormatter::new()
+    ///     .region(FrenchRegion::Canada)
+    ///     .format_tex("« Quoi ? » Voici : rien.");
+    /// assert_eq!(&s, "«~Quoi?~» Voici~: rien.");
+    /// ```
+    ///
+    /// Default is `FrenchRegion::France`.
+    pub fn region(&mut self, region: FrenchRegion) -> &mut Self {
+        self.region = region;
         self
     }
 
-    /// Sets the threshold for unit.
+    /// Sets the TeX dialect used by `format_tex`.
     ///
-    /// After that number of characters, assume it's not an unit.
+    /// # Example
+    ///
+    /// ```
+    /// use crowbook_text_processing::FrenchFormatter;
+    /// use crowbook_text_processing::escape::TexDialect;
+    /// let s = FrenchFormatter::new()
+    ///     .tex_dialect(TexDialect::UnicodeLatex)
+    ///     .format_tex("« Le #1 ? »");
+    /// assert_eq!(&s, "«\u{A0}Le \\#1\u{202F}?\u{A0}»");
+    /// ```
     ///
-    /// Default is `2`.
-    pub fn threshold_unit(&mut self, t: usize) -> &mut Self {
-        self.threshold_unit = t;
+    /// Default is `TexDialect::PdfLatex`.
+    pub fn tex_dialect(&mut self, dialect: TexDialect) -> &mut Self {
+        self.tex_dialect = dialect;
         self
     }
 
@@ -128,48 +197,12 @@ impl FrenchFormatter {
         self
     }
 
-    /// Enables the typographic quotes replacement.
-    ///
-    /// If true, "L'" will be replaced by "L’"
-    ///
-    /// Default is true
-    pub fn typographic_quotes(&mut self, b: bool) -> &mut Self {
-        self.typographic_quotes = b;
-        self
-    }
-
-    /// Enables typographic ellipsis replacement.
-    ///
-    /// If true, "..." will be replaced by "…"
-    ///
-    /// Default is true
-    pub fn typographic_ellipsis(&mut self, b: bool) -> &mut Self {
-        self.typographic_ellipsis = b;
-        self
-    }
-
-    /// If set to true, replaces `--`to `–` and `---` to `—`.
-    ///
-    /// Default is false.
-    pub fn ligature_dashes(&mut self, b: bool) -> &mut Self {
-        self.ligature_dashes = b;
-        self
-    }
-
-    /// If set to true, replaces `<<` to `«` and `>>` to `»`.
-    ///
-    /// Default is false.
-    pub fn ligature_guillemets(&mut self, b: bool) -> &mut Self {
-        self.ligature_guillemets = b;
-        self
-    }
-
     /// (Try to) Format a string according to french typographic rules.
     ///
     /// This method should be called for each paragraph, as it makes some suppositions that
     /// the beginning of the string also means the beginning of a line.
     ///
-    /// This method calls `remove_whitespaces` internally, as it relies on it.
+    /// This method calls `clean::whitespaces` internally, as it relies on it.
     ///
     /// # Example
     ///
@@ -182,23 +215,7 @@ impl FrenchFormatter {
     /// println!("{}", s);
     /// ```
     pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
-        let mut input = clean::whitespaces(input); // first pass to remove whitespaces
-
-        if self.ligature_dashes {
-            input = clean::dashes(input);
-        }
-
-        if self.ligature_guillemets {
-            input = clean::guillemets(input);
-        }
-
-        if self.typographic_quotes {
-            input = clean::quotes(input);
-        }
-
-        if self.typographic_ellipsis {
-            input = clean::ellipsis(input);
-        }
+        let input = self.pipeline.run(input.into(), &FRENCH_QUOTES);
 
         // Find first characters that are trouble
         let first = input.chars().position(is_trouble);
@@ -212,35 +229,14 @@ impl FrenchFormatter {
         let (nb_char, nb_char_em, nb_char_narrow) = (NB_CHAR, NB_CHAR_EM, NB_CHAR_NARROW);
 
         let mut chars = input.chars().collect::<Vec<_>>();
-        let mut is_number_series = false;
+        // Spaces that must be removed
+        let mut removed = vec![false; chars.len()];
 
         // Handle numbers
         if let Some(first) = first_number {
-            // Go back one step
-            let first = if first > 1 { first - 1 } else { 0 };
-            for i in first..(chars.len() - 1) {
-                // Handle numbers (that's easy)
-                let current = chars[i];
-                let next = chars[i + 1];
-
-                match current {
-                    '0'..='9' => {
-                        if i == 0 || !chars[i - 1].is_alphabetic() {
-                            is_number_series = true;
-                        }
-                    }
-                    c if c.is_whitespace() => {
-                        if is_number_series &&
-                           (next.is_digit(10) || self.char_is_symbol(&chars, i + 1)) {
-                            // Next char is a number or symbol such as $, and previous was number
-                            chars[i] = nb_char_narrow;
-                        }
-                    }
-                    _ => {
-                        is_number_series = false;
-                    }
-                }
-            }
+            common::format_numbers(&mut chars, first, nb_char_narrow,
+                                   self.pipeline.threshold_currency,
+                                   self.pipeline.threshold_unit);
         }
 
         // Handle the rest
@@ -253,13 +249,18 @@ impl FrenchFormatter {
                 if is_whitespace(current) {
                     match next {
                         // handle narrow nb space before char
-                        '?' | '!' | ';' => chars[i] = nb_char_narrow,
+                        '?' | '!' | ';' => match self.region.space_before_punctuation() {
+                            Some(c) => chars[i] = c,
+                            None => removed[i] = true,
+                        },
+                        // Non-breaking space before `:`, in all regions
                         ':' => chars[i] = nb_char,
                         '»' => {
                             if current == ' ' {
                                 // Assumne that if it isn't a normal space it
                                 // was used here for good reason, don't replace it
-                                chars[i] = nb_char;
+                                chars[i] = self.region.space_inside_guillemets()
+                                    .unwrap_or(nb_char);
                             }
                         }
                         _ => (),
@@ -289,7 +290,12 @@ impl FrenchFormatter {
                                         let j = find_next(&chars, '»', i);
                                         if let Some(j) = j {
                                             if chars[j - 1].is_whitespace() {
-                                                if i <= 1 ||
+                                                if let Some(c) = self.region
+                                                    .space_inside_guillemets() {
+                                                        // Fixed by region
+                                                        chars[j - 1] = c;
+                                                        c
+                                                    } else if i <= 1 ||
                                                     j - i > self.threshold_quote {
                                                         // Either '«' was at the beginning
                                                         // => assume it is a dialogue
@@ -309,7 +315,8 @@ impl FrenchFormatter {
                                             }
                                         } else {
                                             // No ending quote found, assume is a dialogue
-                                            nb_char
+                                            self.region.space_inside_guillemets()
+                                                .unwrap_or(nb_char)
                                         }
                                     }, // TODO: better heuristic: use narrow nb_char if not at front?
                                     _ => unreachable!(),
@@ -322,13 +329,18 @@ impl FrenchFormatter {
                 }
             }
         }
-        Cow::Owned(chars.into_iter().collect())
+        Cow::Owned(chars.into_iter()
+                   .zip(removed)
+                   .filter(|(_, removed)| !removed)
+                   .map(|(c, _)| c)
+                   .collect())
     }
 
     /// (Try to) Format a string according to french typographic rules, escape the characters
     /// that need to be escaped in LaTeX (e.g. backslashes) and use TeX commands ("~", "\enspace" "and "\,")
     /// for non-breaking spaces so it works correctly with some LaTeX versions (and it makes
-    /// the non-breaking spaces shenanigans more visible with most editors)
+    /// the non-breaking spaces shenanigans more visible with most editors). The commands
+    /// depend on the TeX dialect, see `tex_dialect`.
     ///
     /// # Example
     ///
@@ -339,54 +351,24 @@ impl FrenchFormatter {
     /// assert_eq!(&s, "«~Est-ce bien formaté\\,?~»");
     /// ```
     pub fn format_tex<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
-        escape::nb_spaces_tex(escape::tex(self.format(input)))
-    }
-
-    /// (Try to) Format a string according to french typographic rules, and escape the characters
-    /// that need to be escaped in HTML (e.g. &). Also use HTML commands instead
-    /// of unicode for narrow non-breaking spaces. See `escape::nb_spaces_html`. It's a bit of a hack
-    /// to make it work in most browsers/ereaders.
-    pub fn format_html<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
-        escape::nb_spaces_html(escape::html(self.format(input)))
+        escape::nb_spaces_tex_with(escape::tex_with(self.format(input), self.tex_dialect),
+                                   self.tex_dialect)
     }
 
-
-    /// Return true if the character is a symbol that is used after number
-    /// and should have a nb_char before
-    fn char_is_symbol(&self, v: &[char], i: usize) -> bool {
-        let is_next_letter = if i < v.len() - 1 {
-            v[i + 1].is_alphabetic()
-        } else {
-            false
-        };
-        if is_next_letter {
-            match v[i] {
-                '°' => true,
-                c if c.is_uppercase() => {
-                    let word = get_next_word(v, i);
-                    if word.len() > self.threshold_currency {
-                        // not a currency
-                        false
-                    } else {
-                        // if all uppercase and less than THRESHOLD,
-                        // assume it's a currency or a unit
-                        word.iter().all(|c| c.is_uppercase())
-                    }
-                }
-                c if c.is_alphabetic() => {
-                    let word = get_next_word(v, i);
-                    // if two letters, assume it is a unit
-                    word.len() <= self.threshold_unit
-                }
-                _ => false,
-            }
-        } else {
-            match v[i] {
-                c if (!c.is_alphabetic() && !c.is_whitespace()) => true, // special symbol
-                c if c.is_uppercase() => true, //single uppercase letter
-                _ => false,
-            }
-        }
+    /// (Try to) Format a string according to french typographic rules, escape the characters
+    /// that need to be escaped in Typst (e.g. `#`) and use `~` and `#h()` for non-breaking
+    /// spaces. See `escape::typst` and `escape::nb_spaces_typst`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crowbook_text_processing::FrenchFormatter;
+    /// let f = FrenchFormatter::new();
+    /// let s = f.format_typst("« Est-ce bien le #1 ? »");
+    /// assert_eq!(&s, "«~Est-ce bien le \\#1\u{202F}?~»");
+    /// ```
+    pub fn format_typst<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        escape::nb_spaces_typst(escape::typst(self.format(input)))
     }
 
     // Return Some(pos) if a closing dash was found before what looks
@@ -422,6 +404,8 @@ impl FrenchFormatter {
     }
 }
 
+typographic_formatter!(FrenchFormatter, [html, numbers]);
+
 fn is_trouble(c: char) -> bool {
     match c {
         '?' | '!' | ';' | ':' | '»' | '«' | '—' | '–' => true,
@@ -460,33 +444,6 @@ fn is_next_char_uppercase(v: &[char], n: usize) -> bool {
 }
 
 
-/// Returns the next word in `v` starting from index `n`
-fn get_next_word(v: &[char], n: usize) -> &[char] {
-    let mut beginning = n;
-    let mut end = v.len();
-
-    for (i, car) in v.iter()
-        .enumerate()
-        .skip(n) {
-        if car.is_alphabetic() {
-            beginning = i;
-            break;
-        }
-    }
-
-    for (i, car) in v.iter()
-        .enumerate()
-        .skip(beginning) {
-        if car.is_whitespace() {
-            end = i - 1;
-            break;
-        }
-    }
-
-    &v[beginning..end]
-}
-
-
 #[cfg(test)]
 #[test]
 fn french() {
@@ -507,6 +464,17 @@ fn french_quotes_1() {
     assert_eq!(&res, "«~Un test~»");
 }
 
+#[test]
+fn french_quotes_elisions() {
+    let s = FrenchFormatter::new().format("C'est 'n' importe quoi");
+    assert_eq!(&s, "C’est ‘n’ importe quoi");
+
+    let s = FrenchFormatter::new()
+        .add_elisions(&["'n'"])
+        .format("C'est 'n' importe quoi");
+    assert_eq!(&s, "C’est ’n’ importe quoi");
+}
+
 #[test]
 fn french_quotes_2() {
     let s = "« Un test";
@@ -595,3 +563,23 @@ fn french_numbers() {
     let res = french.format_tex(s);
     assert_eq!(&res, "20 BALLES");
 }
+
+#[test]
+fn french_regions() {
+    let s = "« Comment ? » Elle répondit : « Très bien, ma chère ; et vous ? »";
+
+    let res = FrenchFormatter::new()
+        .region(FrenchRegion::Belgium)
+        .format_tex(s);
+    assert_eq!(&res, "«~Comment\\,?~» Elle répondit~: «~Très bien, ma chère\\,; et vous\\,?~»");
+
+    let res = FrenchFormatter::new()
+        .region(FrenchRegion::Canada)
+        .format_tex(s);
+    assert_eq!(&res, "«~Comment?~» Elle répondit~: «~Très bien, ma chère; et vous?~»");
+
+    let res = FrenchFormatter::new()
+        .region(FrenchRegion::Switzerland)
+        .format_tex(s);
+    assert_eq!(&res, "«\\,Comment\\,?\\,» Elle répondit~: «\\,Très bien, ma chère\\,; et vous\\,?\\,»");
+}
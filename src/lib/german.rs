@@ -0,0 +1,200 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::default::Default;
+
+use regex::{Regex, Captures};
+
+use crate::common;
+use crate::common::Pipeline;
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used by default in german: „…“ and ‚…‘
+const LOW_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('„', '“'),
+    secondary: ('‚', '‘'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Quotation marks frequently used in german books: »…« and ›…‹
+const GUILLEMET_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('»', '«'),
+    secondary: ('›', '‹'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Common german abbreviations whose parts must be separated with a narrow
+/// non-breaking space.
+const ABBREVIATIONS: &[&str] = &["z. B.", "d. h.", "u. a.", "o. Ä.", "z. T.", "u. U.",
+                                 "s. o.", "s. u.", "v. a.", "u. v. m.", "i. d. R.",
+                                 "m. E.", "o. g.", "z. Zt."];
+
+/// German typographic formatter.
+///
+/// The purpose of this struct is to try to make a text more typographically correct,
+/// according to german typographic rules. This means:
+///
+/// * replacing straight quotes with german ones, `„…“` (and `‚…‘` for nested quotes), or,
+///   if the `guillemet_quotes` option is set, `»…«` (and `›…‹`);
+/// * making spaces in abbreviations such as `z. B.` or `d. h.` narrow and non-breaking
+///   (and adding them if they are missing, e.g. `z.B.`);
+/// * making spaces in numbers, e.g. `80 000` or `50 €` narrow and non-breaking.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::GermanFormatter;
+/// let input = "Er sagte: \"Das kostet z.B. 50 €.\"";
+/// let output = GermanFormatter::new()
+///              .format_tex(input); // format to tex (so non-breaking
+///                                  // spaces are visible in assert_eq!)
+/// assert_eq!(&output, "Er sagte: „Das kostet z.\\,B. 50\\,€.“");
+/// ```
+#[derive(Debug, Default)]
+pub struct GermanFormatter {
+    /// Use »…« instead of „…“
+    guillemet_quotes: bool,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
+}
+
+impl GermanFormatter {
+    /// Create a new GermanFormatter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// If set to true, replaces straight quotes with `»…«` (and `›…‹` for nested
+    /// quotes), as is frequent in books, instead of `„…“` (and `‚…‘`).
+    ///
+    /// Default is false.
+    pub fn guillemet_quotes(&mut self, b: bool) -> &mut Self {
+        self.guillemet_quotes = b;
+        self
+    }
+
+    /// (Try to) Format a string according to german typographic rules.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crowbook_text_processing::GermanFormatter;
+    /// let f = GermanFormatter::new();
+    /// let s = f.format("\"Wie geht's?\", fragte sie.");
+    /// assert_eq!(&s, "„Wie geht’s?“, fragte sie.");
+    /// ```
+    pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        lazy_static! {
+            static ref REGEX: Regex = {
+                // First letter might be capitalized at the beginning of a sentence
+                let abbreviations = ABBREVIATIONS.iter()
+                    .map(|a| format!("(?i:{}){}",
+                                     &a[0..1],
+                                     a[1..].split(". ")
+                                     .map(regex::escape)
+                                     .collect::<Vec<_>>()
+                                     .join(r"\.[ \x{A0}\x{202F}]?")))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                Regex::new(&format!(r"\b(?:{})", abbreviations)).unwrap()
+            };
+        }
+
+        let marks = if self.guillemet_quotes {
+            &GUILLEMET_QUOTES
+        } else {
+            &LOW_QUOTES
+        };
+        let mut input = self.pipeline.run(input.into(), marks);
+
+        // Handle abbreviations
+        if REGEX.is_match(&input) {
+            let res = REGEX.replace_all(&input, |caps: &Captures| {
+                let parts = caps[0].trim_end_matches('.')
+                    .split('.')
+                    .map(|s| s.trim_start_matches(common::is_whitespace))
+                    .collect::<Vec<_>>();
+                format!("{}.", parts.join(".\u{202F}"))
+            });
+            input = Cow::Owned(res.into_owned());
+        }
+
+        self.pipeline.numbers(input)
+    }
+}
+
+typographic_formatter!(GermanFormatter, [tex, html, typst, numbers]);
+
+
+#[test]
+fn german_quotes_1() {
+    let s = GermanFormatter::new().format("\"Hallo\", sagte er.");
+    assert_eq!(&s, "„Hallo“, sagte er.");
+}
+
+#[test]
+fn german_quotes_2() {
+    let s = GermanFormatter::new().format("\"Er sagte 'Hallo'\"");
+    assert_eq!(&s, "„Er sagte ‚Hallo‘“");
+}
+
+#[test]
+fn german_quotes_nested_single() {
+    let s = GermanFormatter::new().format("'Er sagte 'nein' dann.'");
+    assert_eq!(&s, "‚Er sagte ’nein’ dann.‘");
+}
+
+#[test]
+fn german_quotes_guillemets() {
+    let s = GermanFormatter::new()
+        .guillemet_quotes(true)
+        .format("\"Er sagte 'Hallo'\"");
+    assert_eq!(&s, "»Er sagte ›Hallo‹«");
+}
+
+#[test]
+fn german_abbreviations() {
+    let f = GermanFormatter::new();
+
+    let s = f.format_tex("Obst, z. B. Äpfel");
+    assert_eq!(&s, "Obst, z.\\,B. Äpfel");
+
+    let s = f.format_tex("Obst, z.B. Äpfel");
+    assert_eq!(&s, "Obst, z.\\,B. Äpfel");
+
+    let s = f.format_tex("Z. B. Äpfel, u.v.m.");
+    assert_eq!(&s, "Z.\\,B. Äpfel, u.\\,v.\\,m.");
+
+    let s = f.format_tex("Es ist so. Ja.");
+    assert_eq!(&s, "Es ist so. Ja.");
+}
+
+#[test]
+fn german_numbers() {
+    let f = GermanFormatter::new();
+
+    let s = f.format_tex("10 000 €");
+    assert_eq!(&s, "10\\,000\\,€");
+
+    let s = f.format_tex("20 °C");
+    assert_eq!(&s, "20\\,°C");
+
+    let s = f.format_tex("10 000 Euro");
+    assert_eq!(&s, "10\\,000 Euro");
+}
+
+#[test]
+fn german_tex() {
+    let s = GermanFormatter::new().format_tex("Das sind 10 000 km, d. h. sehr weit.");
+    assert_eq!(&s, "Das sind 10\\,000\\,km, d.\\,h. sehr weit.");
+}
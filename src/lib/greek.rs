@@ -0,0 +1,74 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+
+use regex::Regex;
+
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used in greek: «…» and “…”
+const GREEK_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('«', '»'),
+    secondary: ('“', '”'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+simple_formatter! {
+    /// Greek typographic formatter.
+    ///
+    /// The purpose of this struct is to try to make a text more typographically correct,
+    /// according to greek typographic rules. This means:
+    ///
+    /// * replacing straight quotes with `«…»` (and `“…”` for nested quotes);
+    /// * removing spaces inside guillemets and before punctuation marks, including the
+    ///   greek question mark (`;`) and the ano teleia (`·`), which, contrary to french, must not
+    ///   be preceded by a space;
+    /// * making spaces in numbers, e.g. `80 000` or `50 km` narrow and non-breaking.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crowbook_text_processing::GreekFormatter;
+    /// let output = GreekFormatter::new()
+    ///              .format("\"Τι κάνεις ;\" ρώτησε.");
+    /// assert_eq!(&output, "«Τι κάνεις;» ρώτησε.");
+    /// ```
+    GreekFormatter, GREEK_QUOTES, remove_spaces
+}
+
+/// Remove spaces before punctuation marks and inside guillemets
+fn remove_spaces(input: Cow<str>) -> Cow<str> {
+    lazy_static! {
+        static ref REGEX: Regex = Regex::new(
+            r"[ \x{A0}\x{202F}]+([;\x{37E}\x{387}·,.:!»])|(«)[ \x{A0}\x{202F}]+").unwrap();
+    }
+    if REGEX.is_match(&input) {
+        Cow::Owned(REGEX.replace_all(&input, "$1$2").into_owned())
+    } else {
+        input
+    }
+}
+
+
+#[test]
+fn greek_punctuation() {
+    let f = GreekFormatter::new();
+
+    let s = f.format("« Γεια σου » , είπε · τι κάνεις ;");
+    assert_eq!(&s, "«Γεια σου», είπε· τι κάνεις;");
+
+    let s = f.format_tex("Τι κάνεις; Καλά.");
+    assert_eq!(&s, "Τι κάνεις; Καλά.");
+}
+
+#[test]
+fn greek_quotes() {
+    let s = GreekFormatter::new().format("\"Είπε 'ναι'\"");
+    assert_eq!(&s, "«Είπε “ναι”»");
+}
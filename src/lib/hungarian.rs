@@ -0,0 +1,49 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used in hungarian: „…”, »…« and ’…’
+const HUNGARIAN_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('„', '”'),
+    secondary: ('»', '«'),
+    tertiary: Some(('’', '’')),
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+simple_formatter! {
+    /// Hungarian typographic formatter.
+    ///
+    /// The purpose of this struct is to try to make a text more typographically correct,
+    /// according to hungarian typographic rules. This means:
+    ///
+    /// * replacing straight quotes with `„…”`, and `»…«` then `’…’` for nested quotes (single
+    ///   quotes, `'`, are also replaced by `»…«`);
+    /// * making spaces in numbers, e.g. `80 000` or `50 km` narrow and non-breaking.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crowbook_text_processing::HungarianFormatter;
+    /// let output = HungarianFormatter::new()
+    ///              .format("\"Azt mondta: \"igen\"\"");
+    /// assert_eq!(&output, "„Azt mondta: »igen«”");
+    /// ```
+    HungarianFormatter, HUNGARIAN_QUOTES
+}
+
+
+#[test]
+fn hungarian_quotes() {
+    let f = HungarianFormatter::new();
+
+    let s = f.format("\"Azt mondta: 'igen'\"");
+    assert_eq!(&s, "„Azt mondta: »igen«”");
+
+    let s = f.format("\"a \"b \"c\"\"\"");
+    assert_eq!(&s, "„a »b ’c’«”");
+}
@@ -0,0 +1,72 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used in italian: «…», “…” and ‘…’
+const ITALIAN_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('«', '»'),
+    secondary: ('“', '”'),
+    tertiary: Some(('‘', '’')),
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+simple_formatter! {
+    /// Italian typographic formatter.
+    ///
+    /// The purpose of this struct is to try to make a text more typographically correct,
+    /// according to italian typographic rules. This means:
+    ///
+    /// * replacing straight quotes with italian ones: `«…»`, and `“…”` then `‘…’` for
+    ///   nested quotes (single quotes, `'`, are also replaced by `“…”`), while elisions such
+    ///   as `l'uomo` or `dell'anno` use the typographic apostrophe;
+    /// * making spaces in numbers, e.g. `80 000` or `50 km` narrow and non-breaking.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crowbook_text_processing::ItalianFormatter;
+    /// let output = ItalianFormatter::new()
+    ///              .format_tex("\"L'uomo dell'anno\" corre 10 km al giorno.");
+    /// assert_eq!(&output, "«L’uomo dell’anno» corre 10\\,km al giorno.");
+    /// ```
+    ItalianFormatter, ITALIAN_QUOTES
+}
+
+
+#[test]
+fn italian_quotes() {
+    let f = ItalianFormatter::new();
+
+    let s = f.format("\"Ciao\", disse.");
+    assert_eq!(&s, "«Ciao», disse.");
+
+    let s = f.format("\"Disse 'ciao'\"");
+    assert_eq!(&s, "«Disse “ciao”»");
+}
+
+#[test]
+fn italian_elisions() {
+    let f = ItalianFormatter::new();
+
+    let s = f.format("'l'uomo dell'anno', un po' di pane, nell'800");
+    assert_eq!(&s, "“l’uomo dell’anno”, un po’ di pane, nell’800");
+
+    let s = f.format("dell'\"Unità\"");
+    assert_eq!(&s, "dell’«Unità»");
+}
+
+#[test]
+fn italian_numbers() {
+    let f = ItalianFormatter::new();
+
+    let s = f.format_tex("10 000 €");
+    assert_eq!(&s, "10\\,000\\,€");
+
+    let s = f.format_tex("10 000 euro");
+    assert_eq!(&s, "10\\,000 euro");
+}
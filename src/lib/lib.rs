@@ -70,7 +70,38 @@ pub mod escape;
 pub mod clean;
 pub mod caps;
 
+#[macro_use]
+mod formatter;
 mod french;
+mod english;
+mod german;
+mod spanish;
+mod italian;
+mod portuguese;
+mod west_slavic;
+mod east_slavic;
+mod nordic;
+mod cjk;
+mod dutch;
+mod greek;
+mod hungarian;
+mod catalan;
+mod rtl;
 mod common;
 
-pub use french::FrenchFormatter;
+pub use formatter::{TypographicFormatter, formatter_for_lang, get_formatter, supported_languages};
+pub use french::{FrenchFormatter, FrenchRegion};
+pub use english::{EnglishFormatter, EnglishVariant, EnglishPunctuation, EnglishDashes};
+pub use german::GermanFormatter;
+pub use spanish::SpanishFormatter;
+pub use italian::ItalianFormatter;
+pub use portuguese::{PortugueseFormatter, PortugueseVariant};
+pub use west_slavic::{WestSlavicFormatter, WestSlavicLanguage};
+pub use east_slavic::{EastSlavicFormatter, EastSlavicLanguage};
+pub use nordic::{NordicFormatter, NordicLanguage};
+pub use cjk::{CjkFormatter, CjkLanguage};
+pub use dutch::DutchFormatter;
+pub use greek::GreekFormatter;
+pub use hungarian::HungarianFormatter;
+pub use catalan::CatalanFormatter;
+pub use rtl::{RtlFormatter, RtlLanguage};
@@ -0,0 +1,198 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::default::Default;
+
+use crate::common::Pipeline;
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used in swedish and finnish: ”…” and ’…’
+const SWEDISH_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('”', '”'),
+    secondary: ('’', '’'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Alternative quotation marks used in swedish and finnish: »…» and ›…›
+const SWEDISH_GUILLEMETS: QuoteStyle = QuoteStyle {
+    primary: ('»', '»'),
+    secondary: ('›', '›'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Quotation marks used in danish: »…« and ›…‹
+const DANISH_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('»', '«'),
+    secondary: ('›', '‹'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Quotation marks used in norwegian: «…» and ‘…’
+const NORWEGIAN_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('«', '»'),
+    secondary: ('‘', '’'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Language used by `NordicFormatter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NordicLanguage {
+    /// Swedish (sv)
+    Swedish,
+    /// Norwegian (no, nb, nn)
+    Norwegian,
+    /// Danish (da)
+    Danish,
+    /// Finnish (fi)
+    Finnish,
+}
+
+/// Swedish, norwegian, danish and finnish typographic formatter.
+///
+/// The purpose of this struct is to try to make a text more typographically correct,
+/// according to the typographic rules of these languages. This means:
+///
+/// * replacing straight quotes with the quotation marks of the language:
+///     * `”…”` (and `’…’` for nested quotes) in swedish and finnish, or `»…»` (and `›…›`) if
+///       the `guillemet_quotes` option is set;
+///     * `»…«` (and `›…‹`) in danish;
+///     * `«…»` (and `‘…’`) in norwegian;
+/// * making spaces in numbers, e.g. `80 000` or `50 km` narrow and non-breaking.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::{NordicFormatter, NordicLanguage};
+/// let input = "\"Det kostar 10 000 kr om dagen\", sa hon.";
+/// let output = NordicFormatter::new()
+///              .format_tex(input);
+/// assert_eq!(&output, "”Det kostar 10\\,000\\,kr om dagen”, sa hon.");
+/// let output = NordicFormatter::new()
+///              .language(NordicLanguage::Danish)
+///              .format_tex(input);
+/// assert_eq!(&output, "»Det kostar 10\\,000\\,kr om dagen«, sa hon.");
+/// ```
+#[derive(Debug)]
+pub struct NordicFormatter {
+    /// Swedish, norwegian, danish or finnish
+    language: NordicLanguage,
+    /// Use »…» instead of ”…” (swedish and finnish)
+    guillemet_quotes: bool,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
+}
+
+impl Default for NordicFormatter {
+    fn default() -> Self {
+        NordicFormatter {
+            language: NordicLanguage::Swedish,
+            guillemet_quotes: false,
+            pipeline: Pipeline::default(),
+        }
+    }
+}
+
+impl NordicFormatter {
+    /// Create a new NordicFormatter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the language (swedish, norwegian, danish or finnish).
+    ///
+    /// Default is `NordicLanguage::Swedish`.
+    pub fn language(&mut self, language: NordicLanguage) -> &mut Self {
+        self.language = language;
+        self
+    }
+
+    /// If set to true, replaces straight quotes with `»…»` (and `›…›` for nested
+    /// quotes) instead of `”…”` (and `’…’`).
+    ///
+    /// This only applies to swedish and finnish.
+    ///
+    /// Default is false.
+    pub fn guillemet_quotes(&mut self, b: bool) -> &mut Self {
+        self.guillemet_quotes = b;
+        self
+    }
+
+    /// (Try to) Format a string according to swedish, norwegian, danish or finnish
+    /// typographic rules.
+    pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        let marks = match self.language {
+            NordicLanguage::Swedish | NordicLanguage::Finnish => {
+                if self.guillemet_quotes {
+                    &SWEDISH_GUILLEMETS
+                } else {
+                    &SWEDISH_QUOTES
+                }
+            }
+            NordicLanguage::Danish => &DANISH_QUOTES,
+            NordicLanguage::Norwegian => &NORWEGIAN_QUOTES,
+        };
+        let input = self.pipeline.run(input.into(), marks);
+
+        self.pipeline.numbers(input)
+    }
+}
+
+typographic_formatter!(NordicFormatter, [tex, html, typst, numbers]);
+
+
+#[test]
+fn swedish_quotes() {
+    let f = NordicFormatter::new();
+    let s = f.format("\"Han sa 'hej'\", sa hon.");
+    assert_eq!(&s, "”Han sa ’hej’”, sa hon.");
+}
+
+#[test]
+fn finnish_guillemets() {
+    let s = NordicFormatter::new()
+        .language(NordicLanguage::Finnish)
+        .guillemet_quotes(true)
+        .format("\"Hän sanoi 'hei'\"");
+    assert_eq!(&s, "»Hän sanoi ›hei›»");
+}
+
+#[test]
+fn danish_quotes() {
+    let s = NordicFormatter::new()
+        .language(NordicLanguage::Danish)
+        .guillemet_quotes(true)
+        .format("\"Han sagde 'hej'\"");
+    assert_eq!(&s, "»Han sagde ›hej‹«");
+}
+
+#[test]
+fn norwegian_quotes() {
+    let s = NordicFormatter::new()
+        .language(NordicLanguage::Norwegian)
+        .format("\"Han sa 'hei'\"");
+    assert_eq!(&s, "«Han sa ‘hei’»");
+}
+
+#[test]
+fn nordic_numbers() {
+    let s = NordicFormatter::new().format_tex("1 000 000 invånare");
+    assert_eq!(&s, "1\\,000\\,000 invånare");
+}
@@ -0,0 +1,129 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::default::Default;
+
+use crate::common::Pipeline;
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used in brazilian portuguese: “…” and ‘…’
+const BRAZILIAN_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('“', '”'),
+    secondary: ('‘', '’'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Quotation marks used in european portuguese: «…», “…” and ‘…’
+const EUROPEAN_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('«', '»'),
+    secondary: ('“', '”'),
+    tertiary: Some(('‘', '’')),
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Variant of portuguese used by `PortugueseFormatter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortugueseVariant {
+    /// Brazilian portuguese (pt-BR)
+    Brazilian,
+    /// European portuguese (pt-PT)
+    European,
+}
+
+/// Portuguese typographic formatter.
+///
+/// The purpose of this struct is to try to make a text more typographically correct,
+/// according to portuguese typographic rules. This means:
+///
+/// * replacing straight quotes with `“…”` (and `‘…’` for nested quotes) for brazilian
+///   portuguese, or with `«…»` (and `“…”` then `‘…’`) for european portuguese, while
+///   elisions such as `d'água` use the typographic apostrophe;
+/// * making spaces in numbers, e.g. `80 000` or `50 km` narrow and non-breaking.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::{PortugueseFormatter, PortugueseVariant};
+/// let input = "\"Uma caixa d'água\"";
+/// let output = PortugueseFormatter::new()
+///              .format(input);
+/// assert_eq!(&output, "“Uma caixa d’água”");
+/// let output = PortugueseFormatter::new()
+///              .variant(PortugueseVariant::European)
+///              .format(input);
+/// assert_eq!(&output, "«Uma caixa d’água»");
+/// ```
+#[derive(Debug)]
+pub struct PortugueseFormatter {
+    /// Brazilian or european portuguese
+    variant: PortugueseVariant,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
+}
+
+impl Default for PortugueseFormatter {
+    fn default() -> Self {
+        PortugueseFormatter {
+            variant: PortugueseVariant::Brazilian,
+            pipeline: Pipeline::default(),
+        }
+    }
+}
+
+impl PortugueseFormatter {
+    /// Create a new PortugueseFormatter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the variant of portuguese (brazilian or european).
+    ///
+    /// Default is `PortugueseVariant::Brazilian`.
+    pub fn variant(&mut self, variant: PortugueseVariant) -> &mut Self {
+        self.variant = variant;
+        self
+    }
+
+    /// (Try to) Format a string according to portuguese typographic rules.
+    pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        let marks = match self.variant {
+            PortugueseVariant::Brazilian => &BRAZILIAN_QUOTES,
+            PortugueseVariant::European => &EUROPEAN_QUOTES,
+        };
+        let input = self.pipeline.run(input.into(), marks);
+
+        self.pipeline.numbers(input)
+    }
+}
+
+typographic_formatter!(PortugueseFormatter, [tex, html, typst, numbers]);
+
+
+#[test]
+fn portuguese_brazilian() {
+    let s = PortugueseFormatter::new().format("\"Ele disse 'olá'\"");
+    assert_eq!(&s, "“Ele disse ‘olá’”");
+}
+
+#[test]
+fn portuguese_european() {
+    let s = PortugueseFormatter::new()
+        .variant(PortugueseVariant::European)
+        .format("\"Ele disse 'olá'\"");
+    assert_eq!(&s, "«Ele disse “olá”»");
+}
+
+#[test]
+fn portuguese_numbers() {
+    let s = PortugueseFormatter::new().format_tex("São 10 000 km, d'aqui.");
+    assert_eq!(&s, "São 10\\,000\\,km, d’aqui.");
+}
@@ -0,0 +1,381 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::default::Default;
+
+use crate::common::is_whitespace;
+use crate::common::Pipeline;
+use crate::escape;
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used in hebrew: „…” and ‚…’
+const HEBREW_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('„', '”'),
+    secondary: ('‚', '’'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Quotation marks used in arabic: «…» and “…”
+const ARABIC_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('«', '»'),
+    secondary: ('“', '”'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Language used by `RtlFormatter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtlLanguage {
+    /// Hebrew (he)
+    Hebrew,
+    /// Arabic (ar)
+    Arabic,
+}
+
+/// Hebrew and arabic typographic formatter.
+///
+/// The purpose of this struct is to try to make a text more typographically correct,
+/// according to the typographic rules of these right-to-left languages. This means:
+///
+/// * in hebrew, replacing straight quotes with `„…”` (and `‚…’` for nested quotes), including
+///   after one-letter prefixes (`ו"שלום"`), and replacing quotes used in acronyms and
+///   abbreviations with gershayim and geresh, e.g. `צה"ל` becomes `צה״ל` and `ג'ירפה` becomes
+///   `ג׳ירפה` (see `gershayim`);
+/// * in arabic, replacing straight quotes with `«…»` (and `“…”` for nested quotes), and
+///   replacing commas, question marks and semicolons following arabic words with `،`, `؟`
+///   and `؛`.
+///
+/// Embedded left-to-right runs (numbers, latin words) are only isolated in `format_html`,
+/// the other methods keep the text in logical order, without any control character.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::{RtlFormatter, RtlLanguage};
+/// let output = RtlFormatter::new()
+///              .format("\"שלום\", אמר החייל בצה\"ל.");
+/// assert_eq!(&output, "„שלום”, אמר החייל בצה״ל.");
+/// let output = RtlFormatter::new()
+///              .language(RtlLanguage::Arabic)
+///              .format("قال: \"مرحبا, كيف حالك?\"");
+/// assert_eq!(&output, "قال: «مرحبا، كيف حالك؟»");
+/// ```
+#[derive(Debug)]
+pub struct RtlFormatter {
+    /// Hebrew or arabic
+    language: RtlLanguage,
+    /// Use gershayim and geresh in hebrew acronyms and abbreviations
+    gershayim: bool,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
+}
+
+impl Default for RtlFormatter {
+    fn default() -> Self {
+        RtlFormatter {
+            language: RtlLanguage::Hebrew,
+            gershayim: true,
+            pipeline: Pipeline::default(),
+        }
+    }
+}
+
+impl RtlFormatter {
+    /// Create a new RtlFormatter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the language (hebrew or arabic).
+    ///
+    /// Default is `RtlLanguage::Hebrew`.
+    pub fn language(&mut self, language: RtlLanguage) -> &mut Self {
+        self.language = language;
+        self
+    }
+
+    /// If set to true, replaces straight quotes with gershayim (`״`) or geresh (`׳`) in
+    /// hebrew acronyms and abbreviations, that is:
+    ///
+    /// * a double quote before the last letter of a word (`צה"ל`);
+    /// * a single quote between a letter whose sound it modifies and another letter
+    ///   (`ג'ירפה`);
+    /// * a single quote following a single hebrew letter (`א'`).
+    ///
+    /// This is independent from `typographic_quotes`, so with this option set and
+    /// typographic quotes disabled, quotations keep the straight `"…"` style.
+    ///
+    /// This only applies to hebrew.
+    ///
+    /// Default is true.
+    pub fn gershayim(&mut self, b: bool) -> &mut Self {
+        self.gershayim = b;
+        self
+    }
+
+    /// (Try to) Format a string according to hebrew or arabic typographic rules.
+    pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        match self.language {
+            RtlLanguage::Hebrew => {
+                let mut input = input.into();
+                if self.gershayim {
+                    input = gershayim(input);
+                }
+                if self.pipeline.typographic_quotes {
+                    input = prefixed_quotes(input, self.pipeline.quote_style(&HEBREW_QUOTES));
+                }
+                self.pipeline.run(input, &HEBREW_QUOTES)
+            }
+            RtlLanguage::Arabic => {
+                let input = self.pipeline.run(input.into(), &ARABIC_QUOTES);
+                arabic_punctuation(input)
+            }
+        }
+    }
+
+    /// (Try to) Format a string according to hebrew or arabic typographic rules, and escape the
+    /// characters that need to be escaped in HTML (e.g. &).
+    ///
+    /// If the text contains right-to-left characters, left-to-right runs (latin words, with
+    /// the numbers and neutral characters between them) are wrapped in `<bdi>` elements, so
+    /// they are isolated from the surrounding text and don't mess with the display order of
+    /// neutral characters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crowbook_text_processing::RtlFormatter;
+    /// let s = RtlFormatter::new().format_html("קניתי 3 ספרים ב-Amazon Books.");
+    /// assert_eq!(&s, "קניתי 3 ספרים ב-<bdi>Amazon Books</bdi>.");
+    /// ```
+    pub fn format_html<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        let input = self.format(input);
+        if input.chars().any(is_rtl) {
+            Cow::Owned(isolate_ltr(&input))
+        } else {
+            escape::html(input)
+        }
+    }
+}
+
+typographic_formatter!(RtlFormatter, [tex, typst]);
+
+/// Returns true if the character is a hebrew letter
+fn is_hebrew(c: char) -> bool {
+    matches!(c, '\u{05D0}'..='\u{05EA}' | '\u{05F0}'..='\u{05F2}')
+}
+
+/// Returns true if the character is an arabic letter
+fn is_arabic(c: char) -> bool {
+    matches!(c,
+             '\u{0600}'..='\u{06FF}'
+             | '\u{0750}'..='\u{077F}'
+             | '\u{08A0}'..='\u{08FF}'
+             | '\u{FB50}'..='\u{FDFF}'
+             | '\u{FE70}'..='\u{FEFF}')
+        && c.is_alphabetic()
+}
+
+/// Returns true if the character belongs to a right-to-left script
+fn is_rtl(c: char) -> bool {
+    matches!(c, '\u{0590}'..='\u{05FF}') || is_arabic(c)
+}
+
+/// Returns true if the character is a latin letter or a digit
+fn is_ltr(c: char) -> bool {
+    c.is_ascii_alphanumeric() || (matches!(c, 'À'..='ɏ') && c.is_alphabetic())
+}
+
+/// Replace quotes in hebrew acronyms and abbreviations with gershayim and geresh
+fn gershayim(input: Cow<str>) -> Cow<str> {
+    if !input.contains(['"', '\'']) {
+        return input;
+    }
+    let mut chars = input.chars().collect::<Vec<_>>();
+    let mut changed = false;
+    for i in 1..chars.len() {
+        let c = chars[i];
+        if (c != '"' && c != '\'') || !is_hebrew(chars[i - 1]) {
+            continue;
+        }
+        let next = chars.get(i + 1).cloned();
+        let is_last_letter = next.is_some_and(is_hebrew)
+            && !chars.get(i + 2).is_some_and(|c| c.is_alphabetic());
+        if c == '"' && is_last_letter {
+            // Acronym, e.g. צה"ל
+            chars[i] = '״';
+            changed = true;
+        } else if c == '\'' && next.is_some_and(is_hebrew)
+            && matches!(chars[i - 1], 'ג' | 'ז' | 'צ' | 'ץ' | 'ת' | 'ד' | 'ח') {
+                // Geresh modifying the sound of a letter, e.g. ג'ירפה
+                chars[i] = '׳';
+                changed = true;
+            } else if c == '\''
+            && (i < 2 || !chars[i - 2].is_alphabetic())
+            && !next.is_some_and(|c| c.is_alphabetic()) {
+                // Single letter: abbreviation or number
+                chars[i] = '׳';
+                changed = true;
+            }
+    }
+    if changed {
+        Cow::Owned(chars.into_iter().collect())
+    } else {
+        input
+    }
+}
+
+/// Replace quotes following a one-letter prefix (e.g. `ו"שלום"`), which wouldn't be
+/// recognized as opening quotes, with the primary marks of `style`
+fn prefixed_quotes<'a>(input: Cow<'a, str>, style: &QuoteStyle) -> Cow<'a, str> {
+    if !input.contains('"') {
+        return input;
+    }
+    let mut chars = input.chars().collect::<Vec<_>>();
+    let mut changed = false;
+    for i in 1..chars.len() {
+        let is_prefixed = chars[i] == '"'
+            && matches!(chars[i - 1], 'ב' | 'ה' | 'ו' | 'כ' | 'ל' | 'מ' | 'ש')
+            && (i < 2 || !chars[i - 2].is_alphabetic())
+            && chars.get(i + 1).is_some_and(|c| is_hebrew(*c));
+        if !is_prefixed {
+            continue;
+        }
+        let closing = (i + 2..chars.len())
+            .find(|j| chars[*j] == '"'
+                  && !chars.get(*j + 1).is_some_and(|c| c.is_alphabetic()));
+        if let Some(j) = closing {
+            chars[i] = style.primary.0;
+            chars[j] = style.primary.1;
+            changed = true;
+        }
+    }
+    if changed {
+        Cow::Owned(chars.into_iter().collect())
+    } else {
+        input
+    }
+}
+
+/// Replace commas, question marks and semicolons following an arabic word with the arabic
+/// punctuation marks
+fn arabic_punctuation(input: Cow<str>) -> Cow<str> {
+    if !input.contains([',', '?', ';']) {
+        return input;
+    }
+    let mut chars = input.chars().collect::<Vec<_>>();
+    let mut changed = false;
+    for i in 1..chars.len() {
+        let replacement = match chars[i] {
+            ',' => '،',
+            '?' => '؟',
+            ';' => '؛',
+            _ => continue,
+        };
+        let follows_arabic = chars[..i].iter()
+            .rev()
+            .find(|c| !is_whitespace(**c) && !matches!(**c, '»' | '”' | '"' | ')'))
+            .is_some_and(|c| is_arabic(*c));
+        if follows_arabic {
+            chars[i] = replacement;
+            changed = true;
+        }
+    }
+    if changed {
+        Cow::Owned(chars.into_iter().collect())
+    } else {
+        input
+    }
+}
+
+/// Escape a string for HTML, wrapping left-to-right runs (latin words and numbers, and the
+/// neutral characters between them) in `<bdi>` elements
+///
+/// Runs without any latin letter are not isolated, since numbers are already displayed
+/// left-to-right by the bidirectional algorithm.
+fn isolate_ltr(input: &str) -> String {
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut output = String::with_capacity(input.len());
+    let mut rtl_start = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        if !is_ltr(chars[i]) {
+            i += 1;
+            continue;
+        }
+        // Find the end of the run, up to the last left-to-right character before a
+        // right-to-left one
+        let mut end = i + 1;
+        let mut j = end;
+        while j < chars.len() && !is_rtl(chars[j]) {
+            if is_ltr(chars[j]) {
+                end = j + 1;
+            }
+            j += 1;
+        }
+        if !chars[i..end].iter().any(|c| c.is_alphabetic()) {
+            i = end;
+            continue;
+        }
+        let rtl = chars[rtl_start..i].iter().collect::<String>();
+        let ltr = chars[i..end].iter().collect::<String>();
+        output.push_str(&escape::html(rtl));
+        output.push_str("<bdi>");
+        output.push_str(&escape::html(ltr));
+        output.push_str("</bdi>");
+        rtl_start = end;
+        i = end;
+    }
+    let rtl = chars[rtl_start..].iter().collect::<String>();
+    output.push_str(&escape::html(rtl));
+    output
+}
+
+
+#[test]
+fn hebrew_quotes() {
+    let s = RtlFormatter::new().format("\"הוא אמר 'כן'\"...");
+    assert_eq!(&s, "„הוא אמר ‚כן’”…");
+}
+
+#[test]
+fn hebrew_gershayim() {
+    let f = RtlFormatter::new();
+    let s = f.format("צה\"ל, ג'ירפה, סעיף א' ו\"שלום\"");
+    assert_eq!(&s, "צה״ל, ג׳ירפה, סעיף א׳ ו„שלום”");
+
+    let s = RtlFormatter::new()
+        .typographic_quotes(false)
+        .format("\"שלום\" בצה\"ל");
+    assert_eq!(&s, "\"שלום\" בצה״ל");
+}
+
+#[test]
+fn arabic_punctuation_marks() {
+    let s = RtlFormatter::new()
+        .language(RtlLanguage::Arabic)
+        .format("\"نعم\", قال; هل تريد 1,000 dollars, أو لا?");
+    assert_eq!(&s, "«نعم»، قال؛ هل تريد 1,000 dollars, أو لا؟");
+}
+
+#[test]
+fn rtl_html() {
+    let f = RtlFormatter::new();
+    let s = f.format_html("מחיר: 20 ש\"ח & 5 USD.");
+    assert_eq!(&s, "מחיר: 20 ש״ח &amp; <bdi>5 USD</bdi>.");
+
+    let s = f.format_html("אם a<b או x = 1, y = 2 אז");
+    assert_eq!(&s, "אם <bdi>a&lt;b</bdi> או <bdi>x = 1, y = 2</bdi> אז");
+
+    let s = f.format_html("Only <latin> text");
+    assert_eq!(&s, "Only &lt;latin&gt; text");
+}
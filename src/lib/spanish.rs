@@ -0,0 +1,259 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::default::Default;
+
+use crate::common::{is_dash, dash_end, skip_whitespaces, is_whitespace};
+use crate::common::Pipeline;
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used in spanish: «…», “…” and ‘…’
+const SPANISH_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('«', '»'),
+    secondary: ('“', '”'),
+    tertiary: Some(('‘', '’')),
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Spanish typographic formatter.
+///
+/// The purpose of this struct is to try to make a text more typographically correct,
+/// according to spanish typographic rules. This means:
+///
+/// * replacing straight quotes with spanish ones: `«…»`, and `“…”` then `‘…’` for
+///   nested quotes (single quotes, `'`, are also replaced by `“…”`);
+/// * replacing dashes (`-`, `--`, `–`) used for dialogue with the raya (`—`), attached to the
+///   text: at the beginning of a paragraph, `- Hola` becomes `—Hola`, and incisos such as
+///   `- dijo él -` become `—dijo él—`.
+///
+/// It can also check that questions and exclamations have their opening `¿` and `¡` (see
+/// `missing_inverted_marks`), though it doesn't try to add them as it can't know where
+/// they should be placed.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::SpanishFormatter;
+/// let output = SpanishFormatter::new()
+///              .format("-- ¿Qué es \"la raya\"? -- preguntó él --. Nadie lo sabía.");
+/// assert_eq!(&output, "—¿Qué es «la raya»? —preguntó él—. Nadie lo sabía.");
+/// ```
+#[derive(Debug)]
+pub struct SpanishFormatter {
+    /// Enable replacement of dialogue dashes
+    dialogue_dashes: bool,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
+}
+
+impl Default for SpanishFormatter {
+    fn default() -> Self {
+        SpanishFormatter {
+            dialogue_dashes: true,
+            pipeline: Pipeline::default(),
+        }
+    }
+}
+
+impl SpanishFormatter {
+    /// Create a new SpanishFormatter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// If set to true, replaces dashes used for dialogue (at the beginning of a paragraph,
+    /// or to open and close an inciso) with the raya (`—`).
+    ///
+    /// Default is true.
+    pub fn dialogue_dashes(&mut self, b: bool) -> &mut Self {
+        self.dialogue_dashes = b;
+        self
+    }
+
+    /// (Try to) Format a string according to spanish typographic rules.
+    ///
+    /// This method should be called for each paragraph, as it makes some suppositions that
+    /// the beginning of the string also means the beginning of a line.
+    pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        let mut input = self.pipeline.run(input.into(), &SPANISH_QUOTES);
+
+        if self.dialogue_dashes && input.contains(is_dash) {
+            let chars = input.chars().collect::<Vec<_>>();
+            input = Cow::Owned(format_dialogue(&chars).into_iter().collect());
+        }
+
+        input
+    }
+
+    /// Returns the sentences ending with `?` (or `!`) that lack their opening `¿` (or `¡`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crowbook_text_processing::SpanishFormatter;
+    /// let f = SpanishFormatter::new();
+    /// let missing = f.missing_inverted_marks("¿Vienes? Qué bien! Si vienes, ¿me avisas?");
+    /// assert_eq!(missing, vec!["Qué bien!"]);
+    /// ```
+    pub fn missing_inverted_marks<'a>(&self, input: &'a str) -> Vec<&'a str> {
+        let mut res = vec![];
+        // Start of current sentence
+        let mut start = 0;
+        let mut opened_question = false;
+        let mut opened_exclamation = false;
+        let mut iter = input.char_indices().peekable();
+        while let Some((i, c)) = iter.next() {
+            match c {
+                '¿' => opened_question = true,
+                '¡' => opened_exclamation = true,
+                '?' | '!' => {
+                    // Handle groups such as `?!` together
+                    let mut end = i + c.len_utf8();
+                    let mut question = c == '?';
+                    let mut exclamation = c == '!';
+                    while let Some(&(j, c)) = iter.peek() {
+                        match c {
+                            '?' => question = true,
+                            '!' => exclamation = true,
+                            _ => break,
+                        }
+                        end = j + c.len_utf8();
+                        iter.next();
+                    }
+                    if (question && !opened_question) || (exclamation && !opened_exclamation) {
+                        res.push(input[start..end].trim_start());
+                    }
+                    start = end;
+                    opened_question = false;
+                    opened_exclamation = false;
+                }
+                '.' | '…' => {
+                    start = i + c.len_utf8();
+                    opened_question = false;
+                    opened_exclamation = false;
+                }
+                _ => (),
+            }
+        }
+        res
+    }
+}
+
+typographic_formatter!(SpanishFormatter, [tex, html, typst]);
+
+/// Replace dialogue dashes with rayas attached to the text
+fn format_dialogue(v: &[char]) -> Vec<char> {
+    let mut res = Vec::with_capacity(v.len());
+    let mut i = skip_whitespaces(v, 0);
+    res.extend_from_slice(&v[0..i]);
+
+    // Dash at the beginning of the paragraph: dialogue
+    if let Some(end) = dash_end(v, i) {
+        let next = skip_whitespaces(v, end);
+        if next < v.len() && !v[next].is_ascii_digit() {
+            res.push('—');
+            i = next;
+        }
+    }
+
+    let mut closing = None;
+    while i < v.len() {
+        if closing == Some(i) {
+            // Closing dash of an inciso: attach it to the previous word
+            while res.last().is_some_and(|c| is_whitespace(*c)) {
+                res.pop();
+            }
+            res.push('—');
+            i = dash_end(v, i).unwrap();
+            let next = skip_whitespaces(v, i);
+            if next < v.len() && matches!(v[next], '.' | ',' | ';' | ':') {
+                i = next;
+            }
+            closing = None;
+            continue;
+        }
+        if closing.is_none() && i > 0 && is_whitespace(v[i - 1]) {
+            if let Some(end) = dash_end(v, i) {
+                let next = skip_whitespaces(v, end);
+                if next < v.len() && v[next].is_alphabetic() {
+                    // Opening dash of an inciso: attach it to the next word
+                    closing = find_closing_dash(v, next);
+                    res.push('—');
+                    i = next;
+                    continue;
+                }
+            }
+        }
+        res.push(v[i]);
+        i += 1;
+    }
+    res
+}
+
+/// Return Some(pos) if a closing dash was found before what looks
+/// like the end of a sentence, None else
+fn find_closing_dash(v: &[char], n: usize) -> Option<usize> {
+    for j in n..v.len() {
+        match v[j] {
+            '.' | '?' | '!' | '…' => return None,
+            '-' if j + 1 < v.len() && v[j - 1].is_alphabetic() && v[j + 1].is_alphabetic() => {
+                // Hyphen in a compound word
+                continue;
+            }
+            c if is_dash(c) => return Some(j),
+            _ => (),
+        }
+    }
+    None
+}
+
+
+#[test]
+fn spanish_quotes() {
+    let f = SpanishFormatter::new();
+
+    let s = f.format("\"Hola\", dijo.");
+    assert_eq!(&s, "«Hola», dijo.");
+
+    let s = f.format("\"Dijo 'hola'\"");
+    assert_eq!(&s, "«Dijo “hola”»");
+
+    let s = f.format("\"Dijo \"lo que \"eso\" dice\"\"");
+    assert_eq!(&s, "«Dijo “lo que ‘eso’ dice”»");
+}
+
+#[test]
+fn spanish_dialogue_1() {
+    let s = SpanishFormatter::new().format("- Hola -dijo él-. ¿Qué tal?");
+    assert_eq!(&s, "—Hola —dijo él—. ¿Qué tal?");
+}
+
+#[test]
+fn spanish_dialogue_2() {
+    let s = SpanishFormatter::new().format("-- Hola -- dijo él. Adiós.");
+    assert_eq!(&s, "—Hola —dijo él. Adiós.");
+}
+
+#[test]
+fn spanish_dialogue_3() {
+    let s = SpanishFormatter::new().format("—Es un hombre franco-alemán —dijo— y muy serio.");
+    assert_eq!(&s, "—Es un hombre franco-alemán —dijo— y muy serio.");
+}
+
+#[test]
+fn spanish_no_dialogue() {
+    let s = SpanishFormatter::new().format("-5 grados, entre 5 - 10 días.");
+    assert_eq!(&s, "-5 grados, entre 5 - 10 días.");
+}
+
+#[test]
+fn spanish_inverted_marks() {
+    let f = SpanishFormatter::new();
+    let missing = f.missing_inverted_marks("—Vienes? —preguntó—. ¡Claro!?");
+    assert_eq!(missing, vec!["—Vienes?", "¡Claro!?"]);
+}
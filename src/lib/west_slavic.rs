@@ -0,0 +1,191 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with
+// this file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use std::borrow::Cow;
+use std::default::Default;
+
+use crate::common::{NB_CHAR, NB_CHAR_NARROW};
+use crate::common;
+use crate::common::is_whitespace;
+use crate::common::Pipeline;
+use crate::clean::QuoteStyle;
+
+/// Quotation marks used in polish: „…” and «…»
+const POLISH_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('„', '”'),
+    secondary: ('«', '»'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Quotation marks used in czech and slovak: „…“ and ‚…‘
+const CZECH_QUOTES: QuoteStyle = QuoteStyle {
+    primary: ('„', '“'),
+    secondary: ('‚', '‘'),
+    tertiary: None,
+    apostrophe: '’',
+    spacing: None,
+    nesting: false,
+    elisions: &[],
+};
+
+/// Language used by `WestSlavicFormatter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WestSlavicLanguage {
+    /// Polish (pl)
+    Polish,
+    /// Czech (cs)
+    Czech,
+    /// Slovak (sk)
+    Slovak,
+}
+
+impl WestSlavicLanguage {
+    /// Returns the one-letter words that must not be left at the end of a line
+    fn one_letter_words(self) -> &'static [char] {
+        match self {
+            WestSlavicLanguage::Polish => &['a', 'i', 'o', 'u', 'w', 'z'],
+            WestSlavicLanguage::Czech
+                | WestSlavicLanguage::Slovak => &['a', 'i', 'k', 'o', 's', 'u', 'v', 'z'],
+        }
+    }
+}
+
+/// Polish, czech and slovak typographic formatter.
+///
+/// The purpose of this struct is to try to make a text more typographically correct,
+/// according to the typographic rules of these languages. This means:
+///
+/// * replacing straight quotes with `„…”` and `«…»` for nested quotes (polish), or
+///   `„…“` and `‚…‘` (czech and slovak);
+/// * making the space after one-letter prepositions and conjunctions (such as `w`,
+///   `z`, `i`, `a`, `k`, `v`…) non-breaking, so they are not left at the end of a line;
+/// * making spaces in numbers, e.g. `80 000` or `50 km` narrow and non-breaking.
+///
+/// # Example
+///
+/// ```
+/// use crowbook_text_processing::{WestSlavicFormatter, WestSlavicLanguage};
+/// let output = WestSlavicFormatter::new()
+///              .format_tex("Mieszkam w \"Krakowie\" i w Warszawie.");
+/// assert_eq!(&output, "Mieszkam w~„Krakowie” i~w~Warszawie.");
+///
+/// let output = WestSlavicFormatter::new()
+///              .language(WestSlavicLanguage::Czech)
+///              .format_tex("Byl v \"Praze\" s kamarádem.");
+/// assert_eq!(&output, "Byl v~„Praze“ s~kamarádem.");
+/// ```
+#[derive(Debug)]
+pub struct WestSlavicFormatter {
+    /// Polish, czech or slovak
+    language: WestSlavicLanguage,
+    /// Generic cleaning steps
+    pipeline: Pipeline,
+}
+
+impl Default for WestSlavicFormatter {
+    fn default() -> Self {
+        WestSlavicFormatter {
+            language: WestSlavicLanguage::Polish,
+            pipeline: Pipeline::default(),
+        }
+    }
+}
+
+impl WestSlavicFormatter {
+    /// Create a new WestSlavicFormatter with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the language (polish, czech or slovak).
+    ///
+    /// Default is `WestSlavicLanguage::Polish`.
+    pub fn language(&mut self, language: WestSlavicLanguage) -> &mut Self {
+        self.language = language;
+        self
+    }
+
+    /// (Try to) Format a string according to polish, czech or slovak typographic rules.
+    pub fn format<'a, S: Into<Cow<'a, str>>>(&self, input: S) -> Cow<'a, str> {
+        let marks = match self.language {
+            WestSlavicLanguage::Polish => &POLISH_QUOTES,
+            WestSlavicLanguage::Czech | WestSlavicLanguage::Slovak => &CZECH_QUOTES,
+        };
+        let input = self.pipeline.run(input.into(), marks);
+
+        let words = self.language.one_letter_words();
+        let is_one_letter_word = |v: &[char], i: usize| {
+            v[i].to_lowercase().all(|c| words.contains(&c))
+                && (i == 0 || is_whitespace(v[i - 1]) || !v[i - 1].is_alphanumeric())
+                && i + 1 < v.len() && is_whitespace(v[i + 1])
+        };
+
+        let mut chars = input.chars().collect::<Vec<_>>();
+        let first_word = (0..chars.len()).find(|i| is_one_letter_word(&chars, *i));
+        let first_number = chars.iter().position(|c| c.is_ascii_digit());
+
+        // No need to do anything, return early
+        if first_word.is_none() && first_number.is_none() {
+            return input;
+        }
+
+        // Handle one-letter words
+        if let Some(first) = first_word {
+            for i in first..chars.len() {
+                if is_one_letter_word(&chars, i) {
+                    chars[i + 1] = NB_CHAR;
+                }
+            }
+        }
+
+        // Handle numbers
+        if let Some(first) = first_number {
+            common::format_numbers(&mut chars, first, NB_CHAR_NARROW,
+                                   self.pipeline.threshold_currency,
+                                   self.pipeline.threshold_unit);
+        }
+
+        Cow::Owned(chars.into_iter().collect())
+    }
+}
+
+typographic_formatter!(WestSlavicFormatter, [tex, html, typst, numbers]);
+
+
+#[test]
+fn polish_one_letter_words() {
+    let f = WestSlavicFormatter::new();
+
+    let s = f.format_tex("A w domu i z kotem, lecz nie k domu.");
+    assert_eq!(&s, "A~w~domu i~z~kotem, lecz nie k domu.");
+
+    let s = f.format_tex("Nic do zrobienia.");
+    assert_eq!(&s, "Nic do zrobienia.");
+}
+
+#[test]
+fn polish_quotes() {
+    let s = WestSlavicFormatter::new().format("\"Powiedział 'tak'\"");
+    assert_eq!(&s, "„Powiedział «tak»”");
+}
+
+#[test]
+fn czech_one_letter_words() {
+    let s = WestSlavicFormatter::new()
+        .language(WestSlavicLanguage::Czech)
+        .format_tex("Šel k řece (s bratrem) a v lese…");
+    assert_eq!(&s, "Šel k~řece (s~bratrem) a~v~lese…");
+}
+
+#[test]
+fn slovak_numbers() {
+    let s = WestSlavicFormatter::new()
+        .language(WestSlavicLanguage::Slovak)
+        .format_html("Stojí 10 000 €.");
+    assert_eq!(&s, "Stojí <span class = \"nnbsp\">10&#160;000&#160;€.</span>");
+}